@@ -34,61 +34,9 @@ const ST_XL_RANGE_MG_MAX: u16 = 1700;
 const ST_GY_RANGE_MDPS_MIN: f32 = 150000.0;
 const ST_GY_RANGE_MDPS_MAX: f32 = 700000.0;
 
-#[maybe_async]
-async fn avg_5_xl_samples<B, T>(sensor: &mut Lsm6dso16is<B, T, MainBank>) -> Result<[f32; 3], Error<B::Error>>
-where
-    B: BusOperation,
-    T: DelayNs,
-{
-    let mut out = [0f32; 3];
-
-    for _ in 0..5 {
-        let mut flag_data = 0;
-        while flag_data == 0 {
-            flag_data = sensor.xl_flag_data_ready_get().await?;
-        }
-
-        let temp_raw = sensor.acceleration_raw_get().await?;
-
-        for i in 0..3 {
-            out[i] += from_fs4g_to_mg(temp_raw[i]);
-        }
-    }
-
-    for i in 0..3 {
-        out[i] /= 5.0;
-    }
-
-    Ok(out)
-}
-
-#[maybe_async]
-async fn avg_5_gy_samples<B, T>(sensor: &mut Lsm6dso16is<B, T, MainBank>) -> Result<[f32; 3], Error<B::Error>>
-where
-    B: BusOperation,
-    T: DelayNs,
-{
-    let mut out = [0f32; 3];
-
-    for _ in 0..5 {
-        let mut flag_data = 0;
-        while flag_data == 0 {
-            flag_data = sensor.gy_flag_data_ready_get().await?;
-        }
-
-        let temp_raw = sensor.angular_rate_raw_get().await?;
-
-        for i in 0..3 {
-            out[i] += from_fs2000dps_to_mdps(temp_raw[i]);
-        }
-    }
-
-    for i in 0..3 {
-        out[i] /= 5.0;
-    }
-
-    Ok(out)
-}
+// Sensitivity at the full scales used by the tests
+const XL_4G_MG_PER_LSB: f32 = 0.122;
+const GY_2000DPS_MDPS_PER_LSB: f32 = 70.0;
 
 #[maybe_async]
 pub async fn run<B, D, L>(bus: B, mut tx: L, mut delay: D, _int_pin: ()) -> !
@@ -143,7 +91,11 @@ where
          * For 5 times, after checking XLDA bit, read the output registers
          * Average the stored data on each axis
          */
-        let out_nost_mg = avg_5_xl_samples(&mut sensor).await.unwrap();
+        let out_nost_mg = sensor
+            .average_samples(5, DataReadySource::Xl, Lsm6dso16is::acceleration_raw_get)
+            .await
+            .unwrap()
+            .map(|lsb| lsb * XL_4G_MG_PER_LSB);
 
         /*
          * Enable xl self-test
@@ -168,7 +120,11 @@ where
          * For 5 times, after checking XLDA bit, read the output registers
          * Average the stored data on each axis
          */
-        let out_st_mg = avg_5_xl_samples(&mut sensor).await.unwrap();
+        let out_st_mg = sensor
+            .average_samples(5, DataReadySource::Xl, Lsm6dso16is::acceleration_raw_get)
+            .await
+            .unwrap()
+            .map(|lsb| lsb * XL_4G_MG_PER_LSB);
 
         /*
          * Disable self-test, disable XL sensor
@@ -227,7 +183,11 @@ where
          * For 5 times, after checking GDA bit, read the output registers
          * Average the stored data on each axis
          */
-        let out_nost_mg = avg_5_gy_samples(&mut sensor).await.unwrap();
+        let out_nost_mg = sensor
+            .average_samples(5, DataReadySource::Gy, Lsm6dso16is::angular_rate_raw_get)
+            .await
+            .unwrap()
+            .map(|lsb| lsb * GY_2000DPS_MDPS_PER_LSB);
 
         /*
          * Enable gy self-test
@@ -252,7 +212,11 @@ where
          * For 5 times, after checking GDA bit, read the output registers
          * Average the stored data on each axis
          */
-        let out_st_mg = avg_5_gy_samples(&mut sensor).await.unwrap();
+        let out_st_mg = sensor
+            .average_samples(5, DataReadySource::Gy, Lsm6dso16is::angular_rate_raw_get)
+            .await
+            .unwrap()
+            .map(|lsb| lsb * GY_2000DPS_MDPS_PER_LSB);
 
         /*
          * Disable self-test, disable sensor
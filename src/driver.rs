@@ -1,10 +1,12 @@
 use super::{
     BusOperation, DelayNs, I2c, MemBankFunctions, RegisterOperation, SensorOperation,
-    SevenBitAddress, SpiDevice, bisync, i2c, prelude::*, register::BankState, spi,
+    SevenBitAddress, SpiDevice, bisync, i2c, only_async, only_sync, prelude::*,
+    register::BankState, spi,
 };
 
 use core::fmt::Debug;
 use core::marker::PhantomData;
+use derive_more::TryFrom;
 
 /// Driver for the Lsm6dso16is sensor.
 ///
@@ -13,6 +15,14 @@ use core::marker::PhantomData;
 /// The bus is generalized over the BusOperation trait, allowing the use
 /// of I2C or SPI protocols; this also allows the user to implement sharing
 /// techniques to share the underlying bus.
+///
+/// The `S` parameter tracks which register bank is selected on the device.
+/// Drivers are always built in the [`MainBank`] state, and every main-page
+/// method is only implemented for `Lsm6dso16is<B, T, MainBank>`: calling one
+/// of them on a `SensorHubBank` or `IspuBank` driver is a compile error
+/// (`no method named ... found`). The other states are only reachable inside
+/// the closures passed to `operate_over_sensor_hub` and `operate_over_ispu`,
+/// which restore the main bank before returning.
 #[bisync]
 pub struct Lsm6dso16is<B, T, S>
 where
@@ -23,6 +33,14 @@ where
     /// The bus driver.
     pub bus: B,
     pub tim: T,
+    startup_discard: u8,
+    /// Samples still to be discarded after the last power-up or ODR change.
+    pending_discard: u8,
+    poll_interval_us: u32,
+    int1_saved: Option<PinInt1Route>,
+    /// Bank last selected through `mem_bank_set`, for `debug_asserts`.
+    #[cfg(feature = "debug_asserts")]
+    bank: MemBank,
     _state: PhantomData<S>,
 }
 
@@ -34,6 +52,51 @@ pub enum Error<B> {
     UnexpectedValue, // Unexpected value read from a register
     FailedToReadMemBank,
     FailedToSetMemBank(MemBank),
+    Timeout, // Status flag not set within the allotted time
+    /// Register `reg` read back `actual` instead of `expected`.
+    RegisterMismatch {
+        reg: u8,
+        expected: u8,
+        actual: u8,
+    },
+}
+
+#[bisync]
+impl<B> Error<B> {
+    /// Returns the underlying bus error, if this is a bus error.
+    pub fn as_bus_error(&self) -> Option<&B> {
+        match self {
+            Error::Bus(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[bisync]
+impl<B> Error<Error<B>> {
+    /// Collapse one level of nesting.
+    ///
+    /// A driver running over `Lsm6dso16isPassthrough` gets `Error<B>` as its
+    /// bus error, so the passthrough errors end up wrapped twice; this turns
+    /// `Bus(inner)` into `inner` and keeps the other variants.
+    pub fn flatten(self) -> Error<B> {
+        match self {
+            Error::Bus(inner) => inner,
+            Error::UnexpectedValue => Error::UnexpectedValue,
+            Error::FailedToReadMemBank => Error::FailedToReadMemBank,
+            Error::FailedToSetMemBank(bank) => Error::FailedToSetMemBank(bank),
+            Error::Timeout => Error::Timeout,
+            Error::RegisterMismatch {
+                reg,
+                expected,
+                actual,
+            } => Error::RegisterMismatch {
+                reg,
+                expected,
+                actual,
+            },
+        }
+    }
 }
 
 #[bisync]
@@ -49,17 +112,30 @@ where
         Self {
             bus,
             tim,
+            startup_discard: 1,
+            pending_discard: 0,
+            poll_interval_us: POLL_INTERVAL_US,
+            int1_saved: None,
+            #[cfg(feature = "debug_asserts")]
+            bank: MemBank::MainMemBank,
             _state: PhantomData,
         }
     }
+
+    /// Change the I2C address used for the following transactions.
+    ///
+    /// Useful when SA0 is switched at runtime, e.g. to talk to two devices
+    /// (0x6A and 0x6B) on the same bus with one driver instance.
+    pub fn set_i2c_address(&mut self, address: I2CAddress) {
+        self.bus.address = address as SevenBitAddress;
+    }
 }
 
 #[bisync]
-impl<B, T, S> Lsm6dso16is<B, T, S>
+impl<B, T> Lsm6dso16is<B, T, MainBank>
 where
     B: BusOperation,
     T: DelayNs,
-    S: BankState,
 {
     /// Constructor method using a generic Bus that implements BusOperation and a generic hardware
     /// timer
@@ -67,6 +143,12 @@ where
         Self {
             bus,
             tim,
+            startup_discard: 1,
+            pending_discard: 0,
+            poll_interval_us: POLL_INTERVAL_US,
+            int1_saved: None,
+            #[cfg(feature = "debug_asserts")]
+            bank: MemBank::MainMemBank,
             _state: PhantomData,
         }
     }
@@ -85,11 +167,63 @@ where
         Self {
             bus,
             tim,
+            startup_discard: 1,
+            pending_discard: 0,
+            poll_interval_us: POLL_INTERVAL_US,
+            int1_saved: None,
+            #[cfg(feature = "debug_asserts")]
+            bank: MemBank::MainMemBank,
             _state: PhantomData,
         }
     }
 }
 
+/// Marker for the buses built by `new_i2c` and `new_spi`, reporting which
+/// interface is used to reach the sensor.
+///
+/// Custom buses passed to `from_bus` can implement it as well to make
+/// `bus_is_i2c`, `interface` and `ui_i2c_mode_set` available.
+#[bisync]
+pub trait BusKind {
+    /// True if the sensor is accessed over I2C.
+    const IS_I2C: bool;
+}
+
+#[bisync]
+impl<P: I2c> BusKind for i2c::I2cBus<P> {
+    const IS_I2C: bool = true;
+}
+
+#[bisync]
+impl<P: SpiDevice> BusKind for spi::SpiBus<P> {
+    const IS_I2C: bool = false;
+}
+
+#[bisync]
+impl<B, T, S> Lsm6dso16is<B, T, S>
+where
+    B: BusOperation + BusKind,
+    T: DelayNs,
+    S: BankState,
+{
+    /// Returns true if the driver talks to the sensor over I2C, false for SPI.
+    pub fn bus_is_i2c(&self) -> bool {
+        B::IS_I2C
+    }
+
+    /// Interface used to reach the sensor, from the bus type.
+    ///
+    /// Lets code generic over the bus skip interface-specific settings,
+    /// e.g. call `spi_mode_set` only over SPI.
+    pub fn interface(&self) -> Interface {
+        if B::IS_I2C {
+            Interface::I2c
+        } else {
+            Interface::Spi
+        }
+    }
+}
+
 #[bisync]
 impl<B, T, S> MemBankFunctions<MemBank> for Lsm6dso16is<B, T, S>
 where
@@ -115,7 +249,14 @@ where
         func_cfg_access
             .write(self)
             .await
-            .map_err(|_| Error::FailedToSetMemBank(val))
+            .map_err(|_| Error::FailedToSetMemBank(val))?;
+
+        #[cfg(feature = "debug_asserts")]
+        {
+            self.bank = val;
+        }
+
+        Ok(())
     }
 
     /// Get the actual MemoryBank set
@@ -141,6 +282,12 @@ impl<B: BusOperation, T: DelayNs, S: BankState> SensorOperation for Lsm6dso16is<
     type Error = Error<B::Error>;
 
     async fn write_to_register(&mut self, reg: u8, buf: &[u8]) -> Result<(), Error<B::Error>> {
+        #[cfg(feature = "debug_asserts")]
+        super::register::assert_reserved_bits(self.bank, reg, buf);
+
+        #[cfg(feature = "log")]
+        log::debug!("write {:#04x}: {:02x?}", reg, buf);
+
         self.bus
             .write_to_register(reg, buf)
             .await
@@ -151,7 +298,12 @@ impl<B: BusOperation, T: DelayNs, S: BankState> SensorOperation for Lsm6dso16is<
         self.bus
             .read_from_register(reg, buf)
             .await
-            .map_err(Error::Bus)
+            .map_err(Error::Bus)?;
+
+        #[cfg(feature = "log")]
+        log::debug!("read {:#04x}: {:02x?}", reg, buf);
+
+        Ok(())
     }
 }
 
@@ -160,6 +312,12 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
     /// Difference in percentage of the effective ODR (and timestamp rate)
     /// with respect to the typical. (set)
     /// Step: 0.15%. 8-bit format, 2's complement.
+    ///
+    /// INTERNAL_FREQ_FINE is a read-only register holding the factory
+    /// measurement of the internal oscillator: the write is ignored by the
+    /// device and cannot be used to trim the ODR. To compensate for the
+    /// deviation, scale the nominal ODR (or timestamp LSB) in software with
+    /// the value returned by `freq_fine_get`.
     pub async fn odr_cal_reg_set(&mut self, val: u8) -> Result<(), Error<B::Error>> {
         let mut internal_freq_fine = InternalFreqFine::read(self).await?;
         internal_freq_fine.set_freq_fine(val);
@@ -171,6 +329,9 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
     /// with respect to the typical.
     /// It changes the register `INTERNAL_FREQ_FINE`
     /// Step:  0.15%. 8-bit format, 2's complement.
+    ///
+    /// The value is returned as raw bits: e.g. `0xFF` means -1 step, not
+    /// 255. Prefer `freq_fine_get`, which returns it already signed.
     pub async fn odr_cal_reg_get(&mut self) -> Result<u8, Error<B::Error>> {
         let val: u8 = InternalFreqFine::read(self)
             .await
@@ -179,6 +340,16 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
         Ok(val)
     }
 
+    /// Get INTERNAL_FREQ_FINE as a signed number of 0.15% steps.
+    ///
+    /// The effective ODR (and timestamp rate) is the typical one scaled by
+    /// `1 + 0.0015 * freq_fine`. Ranges from -128 (`0x80`) to 127 (`0x7F`).
+    pub async fn freq_fine_get(&mut self) -> Result<i8, Error<B::Error>> {
+        let val = self.odr_cal_reg_get().await?;
+
+        Ok(val as i8)
+    }
+
     /// Enables pulsed data-ready mode: Latched/Pulsed(~75 us).
     pub async fn data_ready_mode_set(&mut self, val: DataReadyMode) -> Result<(), Error<B::Error>> {
         let mut drdy_pulsed_reg = DrdyPulsedReg::read(self).await?;
@@ -200,7 +371,158 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
         WhoAmI::read(self).await.map(|reg| reg.into())
     }
 
+    /// Get the memory bank currently selected on the device.
+    ///
+    /// The driver keeps the main bank selected: the sensor hub and ISPU banks
+    /// are only entered for the duration of `operate_over_sensor_hub` and
+    /// `operate_over_ispu`. Any other value means a previous access was
+    /// interrupted before the bank was restored.
+    pub async fn current_bank(&mut self) -> Result<MemBank, Error<B::Error>> {
+        self.mem_bank_get().await
+    }
+
+    /// Retrive FUNC_CFG_ACCESS: bank selection (`shub_reg_access`,
+    /// `ispu_reg_access`) and ISPU core reset (`sw_reset_ispu`).
+    ///
+    /// Between driver calls all three fields normally read 0; a set bank bit
+    /// means the device was left in the sensor hub or ISPU bank, e.g. by an
+    /// access interrupted before the main bank was restored.
+    pub async fn func_cfg_access_get(&mut self) -> Result<FuncCfgAccess, Error<B::Error>> {
+        FuncCfgAccess::read(self).await
+    }
+
+    /// Read `out.len()` bytes starting from the main page register `reg`.
+    ///
+    /// Raw access for custom integrations: no bank switching or decoding is
+    /// done, and consecutive registers are read only if IF_INC is enabled
+    /// (default).
+    pub async fn read_bytes(&mut self, reg: u8, out: &mut [u8]) -> Result<(), Error<B::Error>> {
+        self.read_from_register(reg, out).await
+    }
+
+    /// Read `out.len()` bytes starting from `start`, refusing bursts that
+    /// would run into a reserved address.
+    ///
+    /// The main page registers form these gap-free blocks:
+    /// FUNC_CFG_ACCESS..PIN_CTRL (0x01-0x02), DRDY_PULSED_REG (0x0B),
+    /// INT1_CTRL..CTRL7_G (0x0D-0x16), CTRL9_C..STATUS_REG (0x18-0x1E),
+    /// OUT_TEMP_L..OUTZ_H_A (0x20-0x2D), STATUS_MASTER_MAINPAGE (0x39),
+    /// TIMESTAMP0..TIMESTAMP3 (0x40-0x43), MD1_CFG..MD2_CFG (0x5E-0x5F),
+    /// INTERNAL_FREQ_FINE (0x63) and ISPU_DUMMY_CFG_1_L..ISPU_DUMMY_CFG_4_H
+    /// (0x73-0x7A). If the range does not fit in the block of `start`,
+    /// nothing is read and `Error::UnexpectedValue` is returned. Requires
+    /// IF_INC (default on) for bursts longer than one byte.
+    pub async fn read_contiguous(
+        &mut self,
+        start: Reg,
+        out: &mut [u8],
+    ) -> Result<(), Error<B::Error>> {
+        const BLOCKS: [(Reg, Reg); 10] = [
+            (Reg::FuncCfgAccess, Reg::PinCtrl),
+            (Reg::DrdyPulsedReg, Reg::DrdyPulsedReg),
+            (Reg::Int1Ctrl, Reg::Ctrl7G),
+            (Reg::Ctrl9C, Reg::StatusReg),
+            (Reg::OutTempL, Reg::OutzHA),
+            (Reg::StatusMasterMainpage, Reg::StatusMasterMainpage),
+            (Reg::Timestamp0, Reg::Timestamp3),
+            (Reg::Md1Cfg, Reg::Md2Cfg),
+            (Reg::InternalFreqFine, Reg::InternalFreqFine),
+            (Reg::IspuDummyCfg1L, Reg::IspuDummyCfg4H),
+        ];
+
+        let first = start as u8;
+        let last = BLOCKS
+            .iter()
+            .find(|(lo, hi)| (*lo as u8..=*hi as u8).contains(&first))
+            .map(|(_, hi)| *hi as u8)
+            .ok_or(Error::UnexpectedValue)?;
+
+        if out.len() > (last - first) as usize + 1 {
+            return Err(Error::UnexpectedValue);
+        }
+
+        self.read_from_register(first, out).await
+    }
+
+    /// Write `data` starting from the main page register `reg`.
+    ///
+    /// Raw access for custom integrations: no bank switching or encoding is
+    /// done, and consecutive registers are written only if IF_INC is enabled
+    /// (default).
+    pub async fn write_bytes(&mut self, reg: u8, data: &[u8]) -> Result<(), Error<B::Error>> {
+        self.write_to_register(reg, data).await
+    }
+
+    /// Apply a configuration given as `(register, value)` pairs, in order.
+    ///
+    /// Every address is checked first to be a writable main page register:
+    /// PIN_CTRL, DRDY_PULSED_REG, INT1_CTRL, INT2_CTRL, CTRL1_XL..CTRL7_G,
+    /// CTRL9_C, CTRL10_C, MD1_CFG, MD2_CFG or ISPU_DUMMY_CFG_1_L..
+    /// ISPU_DUMMY_CFG_4_H. FUNC_CFG_ACCESS is rejected, since switching the
+    /// memory bank would break the `MainBank` type-state, as are read-only
+    /// and reserved addresses. If one address is not allowed, nothing is
+    /// written and `Error::UnexpectedValue` is returned.
+    pub async fn apply_register_script(
+        &mut self,
+        script: &[(u8, u8)],
+    ) -> Result<(), Error<B::Error>> {
+        const WRITABLE: [Reg; 23] = [
+            Reg::PinCtrl,
+            Reg::DrdyPulsedReg,
+            Reg::Int1Ctrl,
+            Reg::Int2Ctrl,
+            Reg::Ctrl1Xl,
+            Reg::Ctrl2G,
+            Reg::Ctrl3C,
+            Reg::Ctrl4C,
+            Reg::Ctrl5C,
+            Reg::Ctrl6C,
+            Reg::Ctrl7G,
+            Reg::Ctrl9C,
+            Reg::Ctrl10C,
+            Reg::Md1Cfg,
+            Reg::Md2Cfg,
+            Reg::IspuDummyCfg1L,
+            Reg::IspuDummyCfg1H,
+            Reg::IspuDummyCfg2L,
+            Reg::IspuDummyCfg2H,
+            Reg::IspuDummyCfg3L,
+            Reg::IspuDummyCfg3H,
+            Reg::IspuDummyCfg4L,
+            Reg::IspuDummyCfg4H,
+        ];
+
+        if script
+            .iter()
+            .any(|(addr, _)| !WRITABLE.iter().any(|reg| *reg as u8 == *addr))
+        {
+            return Err(Error::UnexpectedValue);
+        }
+
+        for &(reg, val) in script {
+            self.write_to_register(reg, &[val]).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Set the delay between two reads of a status flag in the driver
+    /// polling loops (default `POLL_INTERVAL_US`, 1 ms); 0 is taken as 1 us.
+    ///
+    /// Every wait gives other tasks a chance to run in the async build and
+    /// frees the bus for other devices, at the cost of up to one interval of
+    /// extra latency once the flag is set. The loops are bounded by time
+    /// spent in the delay, so a shorter interval also means more bus
+    /// transactions before a timeout is reported.
+    pub fn set_poll_interval_us(&mut self, us: u32) {
+        self.poll_interval_us = us.max(1);
+    }
+
     /// Software reset. Restore the default values in user registers.
+    ///
+    /// The self-clearing SW_RESET bit is polled for at most
+    /// `SW_RESET_TIMEOUT_MS`; if it is still set, `Error::Timeout` is
+    /// returned.
     pub async fn software_reset(&mut self) -> Result<(), Error<B::Error>> {
         let mut ctrl3_c = Ctrl3C::read(self).await?;
 
@@ -210,10 +532,122 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
         ctrl3_c.set_sw_reset(1);
         ctrl3_c.write(self).await?;
 
-        while {
-            ctrl3_c = Ctrl3C::read(self).await?;
-            ctrl3_c.sw_reset() == 1
-        } {}
+        for _ in 0..(SW_RESET_TIMEOUT_MS * 1000 / self.poll_interval_us).max(1) {
+            if Ctrl3C::read(self).await?.sw_reset() == 0 {
+                return Ok(());
+            }
+            self.tim.delay_us(self.poll_interval_us).await;
+        }
+
+        if Ctrl3C::read(self).await?.sw_reset() == 0 {
+            Ok(())
+        } else {
+            Err(Error::Timeout)
+        }
+    }
+
+    /// Reinitialize the device and wait for the operation to complete.
+    ///
+    /// `boot_set` and `software_reset` are easy to confuse: the software
+    /// reset restores the default value of the user registers, while the
+    /// memory reboot reloads the factory trimming (calibration) parameters
+    /// and leaves the user registers as they are. See `ReinitMode`.
+    ///
+    /// The accelerometer and gyroscope are powered down first, in every
+    /// mode. The reboot takes about 10 ms, after which the self-clearing
+    /// BOOT bit is polled for at most `REBOOT_TIMEOUT_MS`; if it is still
+    /// set, `Error::Timeout` is returned.
+    pub async fn reinitialize(&mut self, mode: ReinitMode) -> Result<(), Error<B::Error>> {
+        self.xl_data_rate_set(XlDataRate::Off).await?;
+        self.gy_data_rate_set(GyDataRate::Off).await?;
+
+        if mode != ReinitMode::SoftwareReset {
+            self.boot_set(1).await?;
+            self.tim.delay_ms(10).await;
+
+            let mut booted = false;
+            for _ in 0..(REBOOT_TIMEOUT_MS * 1000 / self.poll_interval_us).max(1) {
+                if self.boot_get().await? == 0 {
+                    booted = true;
+                    break;
+                }
+                self.tim.delay_us(self.poll_interval_us).await;
+            }
+            if !booted {
+                return Err(Error::Timeout);
+            }
+        }
+
+        if mode != ReinitMode::RebootMemory {
+            self.software_reset().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Apply a full sensor configuration.
+    ///
+    /// Block data update and full scales are written before the ODRs, then
+    /// the INT1 routing is set.
+    pub async fn config_apply(&mut self, cfg: &Config) -> Result<(), Error<B::Error>> {
+        self.block_data_update_set(cfg.bdu as u8).await?;
+        self.xl_full_scale_set(cfg.xl_fs).await?;
+        self.gy_full_scale_set(cfg.gy_fs).await?;
+        self.xl_data_rate_set(cfg.xl_odr).await?;
+        self.gy_data_rate_set(cfg.gy_odr).await?;
+        self.pin_int1_route_set(cfg.int1).await
+    }
+
+    /// Configure a minimal always-on wake-up preset.
+    ///
+    /// Accelerometer at 1.6 Hz in low-power mode, gyroscope off, pulsed
+    /// data-ready and accelerometer data-ready routed on INT1. The other INT1
+    /// sources are left as they are.
+    pub async fn low_power_wakeup_mode(&mut self) -> Result<(), Error<B::Error>> {
+        self.xl_data_rate_set(XlDataRate::_1_6hzLp).await?;
+        self.gy_data_rate_set(GyDataRate::Off).await?;
+        self.data_ready_mode_set(DataReadyMode::Pulsed).await?;
+
+        let mut int1_ctrl = Int1Ctrl::read(self).await?;
+        int1_ctrl.set_int1_drdy_xl(1);
+        int1_ctrl.write(self).await
+    }
+
+    /// Check that the device answers and comes out of a software reset with
+    /// the expected defaults.
+    ///
+    /// Verifies WHO_AM_I, performs `software_reset` and checks CTRL1_XL
+    /// (0x00), CTRL2_G (0x00) and CTRL3_C (0x04, IF_INC set). The first
+    /// mismatch is returned as `Error::RegisterMismatch` with the register
+    /// address and both values. The device is left in its reset
+    /// configuration.
+    pub async fn power_on_self_check(&mut self) -> Result<(), Error<B::Error>> {
+        let id = self.device_id_get().await?;
+        if id != ID {
+            return Err(Error::RegisterMismatch {
+                reg: Reg::WhoAmI as u8,
+                expected: ID,
+                actual: id,
+            });
+        }
+
+        self.software_reset().await?;
+
+        let defaults = [
+            (Reg::Ctrl1Xl, 0x00),
+            (Reg::Ctrl2G, 0x00),
+            (Reg::Ctrl3C, 0x04),
+        ];
+        let ctrl = self.ctrl_registers_get().await?;
+        for ((reg, expected), actual) in defaults.into_iter().zip(ctrl) {
+            if actual != expected {
+                return Err(Error::RegisterMismatch {
+                    reg: reg as u8,
+                    expected,
+                    actual,
+                });
+            }
+        }
 
         Ok(())
     }
@@ -235,6 +669,20 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
 
         Ok(val)
     }
+
+    /// Check whether the device is safe to configure.
+    ///
+    /// Returns `true` once WHO_AM_I reads back `ID` and the BOOT bit has
+    /// cleared; configuration written while either is not the case may be
+    /// lost. Poll this after power-on instead of a fixed delay.
+    pub async fn is_ready(&mut self) -> Result<bool, Error<B::Error>> {
+        if self.device_id_get().await? != ID {
+            return Ok(false);
+        }
+
+        Ok(self.boot_get().await? == 0)
+    }
+
     /// Enable or disable accelerometer high-performance mode.
     pub async fn xl_hm_mode_set(
         &mut self,
@@ -274,6 +722,35 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
         Ok(val)
     }
 
+    /// Change the accelerometer full scale without mixing scales in a sample.
+    ///
+    /// With BDU enabled the output registers hold a sample until it has been
+    /// read completely, so a sample produced at the old scale can still be
+    /// pending when the scale changes, and the first sample after the change
+    /// may straddle it. When BDU is on this method reads out any pending
+    /// sample, changes the scale, then waits for the next sample and
+    /// discards it. With BDU off (or the accelerometer powered down) it
+    /// behaves like `xl_full_scale_set`.
+    pub async fn xl_full_scale_set_safe(&mut self, fs: XlFullScale) -> Result<(), Error<B::Error>> {
+        let bdu = self.block_data_update_get().await? == 1;
+        let xl_on = self.xl_data_rate_get().await? != XlDataRate::Off;
+        if !bdu || !xl_on {
+            return self.xl_full_scale_set(fs).await;
+        }
+
+        if self.xl_flag_data_ready_get().await? == 1 {
+            self.acceleration_raw_get().await?;
+        }
+
+        self.xl_full_scale_set(fs).await?;
+
+        self.data_ready_wait(DataReadySource::Xl, DRDY_TIMEOUT_MS * 1000)
+            .await?;
+        self.acceleration_raw_get().await?;
+
+        Ok(())
+    }
+
     /// Set the accelerometer output data rate (ODR).
     pub async fn xl_data_rate_set(&mut self, val: XlDataRate) -> Result<(), Error<B::Error>> {
         let mut ctrl1_xl = Ctrl1Xl::read(self).await?;
@@ -287,6 +764,10 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
         ctrl1_xl.set_odr_xl(val as u8 & 0xf);
         ctrl1_xl.write(self).await?;
 
+        if val != XlDataRate::Off {
+            self.pending_discard = self.startup_discard;
+        }
+
         Ok(())
     }
 
@@ -302,6 +783,62 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
         Ok(val)
     }
 
+    /// Get the current accelerometer ODR configuration, failing on an
+    /// undefined encoding.
+    ///
+    /// Unlike `xl_data_rate_get`, which reports `Off` for a combination of
+    /// ODR_XL and XL_HM_MODE with no `XlDataRate` variant, this returns
+    /// `Error::UnexpectedValue`.
+    pub async fn xl_data_rate_get_strict(&mut self) -> Result<XlDataRate, Error<B::Error>> {
+        let ctrl1_xl = Ctrl1Xl::read(self).await?;
+        let ctrl6_c = Ctrl6C::read(self).await?;
+
+        let data_rate_selection = (ctrl6_c.xl_hm_mode() << 4) | ctrl1_xl.odr_xl();
+
+        XlDataRate::try_from(data_rate_selection).map_err(|_| Error::UnexpectedValue)
+    }
+
+    /// Check that the accelerometer high-performance mode matches the ODR.
+    ///
+    /// `xl_data_rate_set` updates XL_HM_MODE together with ODR_XL, but a later
+    /// call to `xl_hm_mode_set` can leave a combination with no `XlDataRate`
+    /// encoding (e.g. 1.6 Hz with high-performance enabled), which
+    /// `xl_data_rate_get` reports as `Off`. Returns false in that case; a
+    /// powered-down accelerometer is always consistent.
+    pub async fn xl_mode_consistent(&mut self) -> Result<bool, Error<B::Error>> {
+        let ctrl1_xl = Ctrl1Xl::read(self).await?;
+        let ctrl6_c = Ctrl6C::read(self).await?;
+
+        if ctrl1_xl.odr_xl() == 0 {
+            return Ok(true);
+        }
+
+        let data_rate_selection = (ctrl6_c.xl_hm_mode() << 4) | ctrl1_xl.odr_xl();
+
+        Ok(XlDataRate::try_from(data_rate_selection).is_ok())
+    }
+
+    /// Switch the accelerometer and gyroscope from the given ODRs to their
+    /// low-power equivalents (same rate, high-performance mode off). ODRs
+    /// that are off or already low-power are kept.
+    async fn low_power_odrs_set(
+        &mut self,
+        xl: XlDataRate,
+        gy: GyDataRate,
+    ) -> Result<(), Error<B::Error>> {
+        let xl_lp = match xl as u8 {
+            0 => xl,
+            code => XlDataRate::try_from(code | 0x10).unwrap_or(xl),
+        };
+        let gy_lp = match gy as u8 {
+            0 => gy,
+            code => GyDataRate::try_from(code | 0x10).unwrap_or(gy),
+        };
+
+        self.xl_data_rate_set(xl_lp).await?;
+        self.gy_data_rate_set(gy_lp).await
+    }
+
     /// Enable or disable gyroscope high-performance mode.
     pub async fn gy_hm_mode_set(
         &mut self,
@@ -321,21 +858,34 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
         Ok(val)
     }
     /// Set gyroscope full-scale.
+    ///
+    /// `_125dps` sets FS_125, which overrides FS_G: FS_G is then irrelevant
+    /// and written as 0.
     pub async fn gy_full_scale_set(&mut self, val: GyFullScale) -> Result<(), Error<B::Error>> {
+        let (fs_125, fs_g) = if val.is_125dps() {
+            (1, 0)
+        } else {
+            (0, val as u8 & 0x3)
+        };
+
         let mut ctrl2_g = Ctrl2G::read(self).await?;
-        ctrl2_g.set_fs_g(val as u8 & 0x3);
-        ctrl2_g.set_fs_125(val as u8 >> 4);
+        ctrl2_g.set_fs_g(fs_g);
+        ctrl2_g.set_fs_125(fs_125);
         ctrl2_g.write(self).await?;
 
         Ok(())
     }
 
     /// Get actual gyroscope full-scale configuration.
+    ///
+    /// FS_125 set reads back as `_125dps` whatever the FS_G value.
     pub async fn gy_full_scale_get(&mut self) -> Result<GyFullScale, Error<B::Error>> {
         let ctrl2_g = Ctrl2G::read(self).await?;
-        let value = (ctrl2_g.fs_125() << 4) | ctrl2_g.fs_g();
+        if ctrl2_g.fs_125() == 1 {
+            return Ok(GyFullScale::_125dps);
+        }
 
-        let val = GyFullScale::try_from(value).unwrap_or_default();
+        let val = GyFullScale::try_from(ctrl2_g.fs_g()).unwrap_or_default();
 
         Ok(val)
     }
@@ -353,6 +903,10 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
         ctrl2_g.set_odr_g(val as u8 & 0xf);
         ctrl2_g.write(self).await?;
 
+        if val != GyDataRate::Off {
+            self.pending_discard = self.startup_discard;
+        }
+
         Ok(())
     }
 
@@ -368,6 +922,95 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
         Ok(val)
     }
 
+    /// Get the current gyroscope ODR configuration, failing on an undefined
+    /// encoding instead of reporting `Off` like `gy_data_rate_get`.
+    pub async fn gy_data_rate_get_strict(&mut self) -> Result<GyDataRate, Error<B::Error>> {
+        let ctrl2_g = Ctrl2G::read(self).await?;
+        let ctrl7_g = Ctrl7G::read(self).await?;
+
+        let value = (ctrl7_g.g_hm_mode() << 4) | ctrl2_g.odr_g();
+
+        GyDataRate::try_from(value).map_err(|_| Error::UnexpectedValue)
+    }
+
+    /// Get the accelerometer and gyroscope ODRs together.
+    ///
+    /// CTRL1_XL through CTRL7_G are read in a single burst; the decoding is
+    /// the same as `xl_data_rate_get` and `gy_data_rate_get`. Requires
+    /// register address auto-increment (IF_INC, default on).
+    pub async fn odr_pair_get(&mut self) -> Result<(XlDataRate, GyDataRate), Error<B::Error>> {
+        let mut buf = [0u8; 7];
+        self.read_from_register(Reg::Ctrl1Xl as u8, &mut buf)
+            .await?;
+
+        let ctrl1_xl = Ctrl1Xl::from_bits(buf[0]);
+        let ctrl2_g = Ctrl2G::from_bits(buf[1]);
+        let ctrl6_c = Ctrl6C::from_bits(buf[5]);
+        let ctrl7_g = Ctrl7G::from_bits(buf[6]);
+
+        let xl = XlDataRate::try_from((ctrl6_c.xl_hm_mode() << 4) | ctrl1_xl.odr_xl())
+            .unwrap_or_default();
+        let gy =
+            GyDataRate::try_from((ctrl7_g.g_hm_mode() << 4) | ctrl2_g.odr_g()).unwrap_or_default();
+
+        Ok((xl, gy))
+    }
+
+    /// Set the accelerometer and gyroscope ODRs together.
+    ///
+    /// CTRL1_XL through CTRL7_G are read in one burst, then CTRL6_C/CTRL7_G
+    /// (high-performance mode) and CTRL1_XL/CTRL2_G (ODR) are written with
+    /// two 2-byte bursts, in the same order as `xl_data_rate_set`. Requires
+    /// register address auto-increment (IF_INC, default on).
+    pub async fn odr_pair_set(
+        &mut self,
+        xl: XlDataRate,
+        gy: GyDataRate,
+    ) -> Result<(), Error<B::Error>> {
+        let mut buf = [0u8; 7];
+        self.read_from_register(Reg::Ctrl1Xl as u8, &mut buf)
+            .await?;
+
+        let mut ctrl1_xl = Ctrl1Xl::from_bits(buf[0]);
+        let mut ctrl2_g = Ctrl2G::from_bits(buf[1]);
+        let mut ctrl6_c = Ctrl6C::from_bits(buf[5]);
+        let mut ctrl7_g = Ctrl7G::from_bits(buf[6]);
+
+        ctrl1_xl.set_odr_xl(xl as u8 & 0xf);
+        ctrl2_g.set_odr_g(gy as u8 & 0xf);
+        ctrl6_c.set_xl_hm_mode((xl as u8 >> 4) & 0x1);
+        ctrl7_g.set_g_hm_mode((gy as u8 >> 4) & 0x1);
+
+        self.write_to_register(Reg::Ctrl6C as u8, &[ctrl6_c.into(), ctrl7_g.into()])
+            .await?;
+        self.write_to_register(Reg::Ctrl1Xl as u8, &[ctrl1_xl.into(), ctrl2_g.into()])
+            .await
+    }
+
+    /// Set the accelerometer and gyroscope to the same high-performance ODR.
+    ///
+    /// The slowest rate not below `odr_hz` is selected for both sensors
+    /// (e.g. 52.0 gives `_52hzHp`); `odr_hz` <= 0 powers both down. Returns
+    /// `Error::UnexpectedValue` above 6667 Hz.
+    pub async fn odr_both_at(&mut self, odr_hz: f32) -> Result<(), Error<B::Error>> {
+        const RATES_HZ: [f32; 10] = [
+            12.5, 26.0, 52.0, 104.0, 208.0, 416.0, 833.0, 1667.0, 3333.0, 6667.0,
+        ];
+
+        let code = if odr_hz <= 0.0 {
+            0
+        } else {
+            match RATES_HZ.iter().position(|rate| *rate >= odr_hz) {
+                Some(idx) => idx as u8 + 1,
+                None => return Err(Error::UnexpectedValue),
+            }
+        };
+
+        let xl = XlDataRate::try_from(code).map_err(|_| Error::UnexpectedValue)?;
+        let gy = GyDataRate::try_from(code).map_err(|_| Error::UnexpectedValue)?;
+        self.odr_pair_set(xl, gy).await
+    }
+
     /// Enable/Disable the automatical increment of register address during a multiple byte access
     /// with a serial interface (enabled by default).
     pub async fn auto_increment_set(&mut self, val: u8) -> Result<(), Error<B::Error>> {
@@ -440,6 +1083,30 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
         Ok(val)
     }
 
+    /// Get the actual accelerometer self-test mode, failing on the reserved
+    /// ST_XL encoding instead of reporting `Disable`.
+    pub async fn xl_self_test_get_strict(&mut self) -> Result<XlSelfTest, Error<B::Error>> {
+        let ctrl5_c = Ctrl5C::read(self).await?;
+
+        XlSelfTest::try_from(ctrl5_c.st_xl()).map_err(|_| Error::UnexpectedValue)
+    }
+
+    /// Set the accelerometer self-test mode, refusing to start a self-test
+    /// while the accelerometer is powered down.
+    ///
+    /// Returns `Error::UnexpectedValue` if `val` is not `Disable` and the
+    /// accelerometer ODR is `Off`.
+    pub async fn xl_self_test_set_checked(
+        &mut self,
+        val: XlSelfTest,
+    ) -> Result<(), Error<B::Error>> {
+        if val != XlSelfTest::Disable && self.xl_data_rate_get().await? == XlDataRate::Off {
+            return Err(Error::UnexpectedValue);
+        }
+
+        self.xl_self_test_set(val).await
+    }
+
     /// Set the gyroscope self-test mode.
     pub async fn gy_self_test_set(&mut self, val: GySelfTest) -> Result<(), Error<B::Error>> {
         let mut ctrl5_c = Ctrl5C::read(self).await?;
@@ -458,6 +1125,30 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
         Ok(val)
     }
 
+    /// Get the gyroscope self-test mode, failing on the reserved ST_G
+    /// encoding instead of reporting `Disable`.
+    pub async fn gy_self_test_get_strict(&mut self) -> Result<GySelfTest, Error<B::Error>> {
+        let ctrl5_c = Ctrl5C::read(self).await?;
+
+        GySelfTest::try_from(ctrl5_c.st_g()).map_err(|_| Error::UnexpectedValue)
+    }
+
+    /// Set the gyroscope self-test mode, refusing to start a self-test
+    /// while the gyroscope is powered down.
+    ///
+    /// Returns `Error::UnexpectedValue` if `val` is not `Disable` and the
+    /// gyroscope ODR is `Off`.
+    pub async fn gy_self_test_set_checked(
+        &mut self,
+        val: GySelfTest,
+    ) -> Result<(), Error<B::Error>> {
+        if val != GySelfTest::Disable && self.gy_data_rate_get().await? == GyDataRate::Off {
+            return Err(Error::UnexpectedValue);
+        }
+
+        self.gy_self_test_set(val).await
+    }
+
     /// Enable/Disable pull-up on SDO pin of UI (User Interface).
     pub async fn ui_sdo_pull_up_set(&mut self, val: u8) -> Result<(), Error<B::Error>> {
         let mut pin_ctrl = PinCtrl::read(self).await?;
@@ -473,6 +1164,11 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
         Ok(val)
     }
 
+    /// Retrive PIN_CTRL register, including the SDO pull-up enable.
+    pub async fn pin_ctrl_get(&mut self) -> Result<PinCtrl, Error<B::Error>> {
+        PinCtrl::read(self).await
+    }
+
     /// Set the SPI Serial Interface Mode.
     pub async fn spi_mode_set(&mut self, val: SpiMode) -> Result<(), Error<B::Error>> {
         let mut ctrl3_c = Ctrl3C::read(self).await?;
@@ -492,7 +1188,19 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
     }
 
     /// Enable/Disable I2C on UI (User Interface).
-    pub async fn ui_i2c_mode_set(&mut self, val: UiI2cMode) -> Result<(), Error<B::Error>> {
+    ///
+    /// Disabling I2C while talking to the sensor over I2C would lock the
+    /// driver out until the next power cycle, so it is refused with
+    /// `Error::UnexpectedValue` when the bus is an I2C one (`BusKind`),
+    /// whichever constructor built the driver.
+    pub async fn ui_i2c_mode_set(&mut self, val: UiI2cMode) -> Result<(), Error<B::Error>>
+    where
+        B: BusKind,
+    {
+        if val == UiI2cMode::Disable && B::IS_I2C {
+            return Err(Error::UnexpectedValue);
+        }
+
         let mut ctrl4_c = Ctrl4C::read(self).await?;
         ctrl4_c.set_i2c_disable((val as u8) & 0x1);
         ctrl4_c.write(self).await?;
@@ -524,14 +1232,69 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
         Ok(val)
     }
 
+    /// Enable the timestamp counter together with the accelerometer.
+    ///
+    /// The timestamp counter only advances while at least one sensor is
+    /// running, otherwise it keeps reading zero. The accelerometer ODR is set
+    /// first and the counter enabled afterwards; `XlDataRate::Off` is rejected
+    /// with `Error::UnexpectedValue`.
+    pub async fn timestamp_enable_with_source(
+        &mut self,
+        xl: XlDataRate,
+    ) -> Result<(), Error<B::Error>> {
+        if xl == XlDataRate::Off {
+            return Err(Error::UnexpectedValue);
+        }
+
+        self.xl_data_rate_set(xl).await?;
+        self.timestamp_set(1).await
+    }
+
     /// Get the Timestamp data output.
     pub async fn timestamp_raw_get(&mut self) -> Result<u32, Error<B::Error>> {
         Timestamp::read(self).await.map(|reg| reg.0)
     }
 
-    /// Get the status of all the interrupt sources.
-    pub async fn all_sources_get(&mut self) -> Result<AllSources, Error<B::Error>> {
-        let status_reg = StatusReg::read(self).await?;
+    /// Get the Timestamp data output, guarding against torn reads.
+    ///
+    /// The four TIMESTAMP registers are not covered by BDU: if the counter
+    /// reaches its end count while they are being read, the bytes can come
+    /// from two different counts. TIMESTAMP_ENDCOUNT is read before and
+    /// after the counter, and the value is accepted only if the flag did not
+    /// change in between. After `TIMESTAMP_READ_ATTEMPTS` attempts with the
+    /// flag toggling, `Error::Timeout` is returned.
+    pub async fn timestamp_coherent_get(&mut self) -> Result<u32, Error<B::Error>> {
+        for _ in 0..TIMESTAMP_READ_ATTEMPTS {
+            let before = StatusReg::read(self).await?.timestamp_endcount();
+            let timestamp = Timestamp::read(self).await?.0;
+            let after = StatusReg::read(self).await?.timestamp_endcount();
+
+            if before == after {
+                return Ok(timestamp);
+            }
+        }
+
+        Err(Error::Timeout)
+    }
+
+    /// Get what is needed to map the timestamp counter to wall-clock time.
+    ///
+    /// Returns `(freq_fine, endcount, timestamp)`: the signed
+    /// INTERNAL_FREQ_FINE trim (see `freq_fine_get`), the
+    /// TIMESTAMP_ENDCOUNT flag of STATUS_REG and the raw counter, read with
+    /// one access each. The counter is not checked for torn reads; use
+    /// `timestamp_coherent_get` when that matters.
+    pub async fn timestamp_sync_info(&mut self) -> Result<(i8, bool, u32), Error<B::Error>> {
+        let freq_fine = self.freq_fine_get().await?;
+        let endcount = StatusReg::read(self).await?.timestamp_endcount() == 1;
+        let timestamp = Timestamp::read(self).await?.0;
+
+        Ok((freq_fine, endcount, timestamp))
+    }
+
+    /// Get the status of all the interrupt sources.
+    pub async fn all_sources_get(&mut self) -> Result<AllSources, Error<B::Error>> {
+        let status_reg = StatusReg::read(self).await?;
         let status_sh = StatusMasterMainpage::read(self).await?;
         let ispu = IspuIntStatusMainPage::read(self).await.map(|reg| reg.0)?;
 
@@ -558,6 +1321,49 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
         StatusReg::read(self).await
     }
 
+    /// Read STATUS_REG as a raw byte: bit 0 XLDA, bit 1 GDA, bit 2 TDA.
+    ///
+    /// A single one-byte transaction, the cheapest way for an interrupt
+    /// handler to decide whether there is new data; use `all_sources_get`
+    /// when the other event sources are needed.
+    pub async fn int_status_fast(&mut self) -> Result<u8, Error<B::Error>> {
+        StatusReg::read(self).await.map(|reg| reg.into_bits())
+    }
+
+    /// Gather WHO_AM_I, accelerometer and gyroscope ODR and full scale, BDU
+    /// and the data-ready flags into one struct, e.g. for a one-line health
+    /// log (`defmt::Format` with the `defmt` feature).
+    pub async fn status_summary(&mut self) -> Result<StatusSummary, Error<B::Error>> {
+        let status_reg = StatusReg::read(self).await?;
+
+        Ok(StatusSummary {
+            who_am_i: self.device_id_get().await?,
+            xl_odr: self.xl_data_rate_get().await?,
+            xl_fs: self.xl_full_scale_get().await?,
+            gy_odr: self.gy_data_rate_get().await?,
+            gy_fs: self.gy_full_scale_get().await?,
+            bdu: self.block_data_update_get().await? == 1,
+            xl_drdy: status_reg.xlda() == 1,
+            gy_drdy: status_reg.gda() == 1,
+            temp_drdy: status_reg.tda() == 1,
+        })
+    }
+
+    /// Get the raw content of the control registers CTRL1_XL (0x10) through
+    /// CTRL10_C (0x19).
+    ///
+    /// Index `n` holds the register at address `0x10 + n`. Address 0x17 is
+    /// reserved: it is skipped with two `read_contiguous` bursts and index 7
+    /// is always 0. Requires register address auto-increment (IF_INC,
+    /// default on).
+    pub async fn ctrl_registers_get(&mut self) -> Result<[u8; 10], Error<B::Error>> {
+        let mut buf = [0u8; 10];
+        self.read_contiguous(Reg::Ctrl1Xl, &mut buf[..7]).await?;
+        self.read_contiguous(Reg::Ctrl9C, &mut buf[8..]).await?;
+
+        Ok(buf)
+    }
+
     /// Returns 1 if new accelerometer data is available, otherwise 0.
     pub async fn xl_flag_data_ready_get(&mut self) -> Result<u8, Error<B::Error>> {
         let val: u8 = StatusReg::read(self).await.map(|reg| reg.xlda())?;
@@ -577,11 +1383,74 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
 
         Ok(val)
     }
+
+    /// Wait until new data is available for the given sensor, polling at the
+    /// interval set with `set_poll_interval_us` for at most `timeout_us`
+    /// microseconds, then give up with `Error::Timeout`.
+    async fn data_ready_wait(
+        &mut self,
+        source: DataReadySource,
+        timeout_us: u32,
+    ) -> Result<(), Error<B::Error>> {
+        self.data_ready_poll(source, timeout_us, self.poll_interval_us)
+            .await
+    }
+
+    /// Like `data_ready_wait`, polling every `step_us` instead.
+    ///
+    /// With a `step_us` of 0 the flag is polled back to back, without any
+    /// delay, as many times as the driver poll interval would allow.
+    async fn data_ready_poll(
+        &mut self,
+        source: DataReadySource,
+        timeout_us: u32,
+        step_us: u32,
+    ) -> Result<(), Error<B::Error>> {
+        let polls = match step_us {
+            0 => timeout_us / self.poll_interval_us,
+            step_us => timeout_us / step_us,
+        };
+
+        for _ in 0..polls.max(1) {
+            if self.data_ready_flag(source).await? {
+                return Ok(());
+            }
+            if step_us > 0 {
+                self.tim.delay_us(step_us).await;
+            }
+        }
+
+        if self.data_ready_flag(source).await? {
+            Ok(())
+        } else {
+            Err(Error::Timeout)
+        }
+    }
+
+    /// Read the data-ready flag of the given sensor from STATUS_REG.
+    async fn data_ready_flag(&mut self, source: DataReadySource) -> Result<bool, Error<B::Error>> {
+        let status_reg = StatusReg::read(self).await?;
+        let flag = match source {
+            DataReadySource::Xl => status_reg.xlda(),
+            DataReadySource::Gy => status_reg.gda(),
+            DataReadySource::Temp => status_reg.tda(),
+        };
+
+        Ok(flag == 1)
+    }
+
     /// Get the Temperature data.
     pub async fn temperature_raw_get(&mut self) -> Result<i16, Error<B::Error>> {
         OutTemp::read(self).await.map(|reg| reg.0)
     }
 
+    /// Get the temperature converted to `scale`.
+    pub async fn temperature_get(&mut self, scale: TempScale) -> Result<f32, Error<B::Error>> {
+        let lsb = self.temperature_raw_get().await?;
+
+        Ok(scale.from_celsius(from_lsb_to_celsius(lsb)))
+    }
+
     /// Retrive the Angular rate readings.
     pub async fn angular_rate_raw_get(&mut self) -> Result<[i16; 3], Error<B::Error>> {
         let val = OutXYZG::read(self).await?;
@@ -596,6 +1465,389 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
         Ok([val.x, val.y, val.z])
     }
 
+    /// Retrive the Linear acceleration readings together with a stale flag.
+    ///
+    /// The flag is `true` when XLDA was clear before the read, i.e. the
+    /// sample was already read once. The device has no overrun flag, so
+    /// samples overwritten between two reads (reader slower than the ODR)
+    /// cannot be detected: XLDA only tells whether at least one new sample
+    /// arrived.
+    pub async fn acceleration_raw_checked(&mut self) -> Result<([i16; 3], bool), Error<B::Error>> {
+        let stale = self.xl_flag_data_ready_get().await? == 0;
+        let val = self.acceleration_raw_get().await?;
+
+        Ok((val, stale))
+    }
+
+    /// Get the linear acceleration converted to `unit` using the current
+    /// accelerometer full scale.
+    pub async fn acceleration_get(&mut self, unit: AccelUnit) -> Result<[f32; 3], Error<B::Error>> {
+        let fs = self.xl_full_scale_get().await?;
+        let raw = self.acceleration_raw_get().await?;
+
+        Ok(raw.map(|lsb| unit.from_mg(from_lsb_to_mg(lsb, fs))))
+    }
+
+    /// Get the linear acceleration both as raw counts and in mg, from a single
+    /// read of the output registers.
+    pub async fn acceleration_full(&mut self) -> Result<AccelSample, Error<B::Error>> {
+        let fs = self.xl_full_scale_get().await?;
+        let raw = self.acceleration_raw_get().await?;
+
+        Ok(AccelSample {
+            raw,
+            mg: raw.map(|lsb| from_lsb_to_mg(lsb, fs)),
+        })
+    }
+
+    /// Get the angular rate both as raw counts and in mdps, from a single
+    /// read of the output registers.
+    pub async fn angular_rate_full(&mut self) -> Result<GyroSample, Error<B::Error>> {
+        let fs = self.gy_full_scale_get().await?;
+        let raw = self.angular_rate_raw_get().await?;
+
+        Ok(GyroSample {
+            raw,
+            mdps: raw.map(|lsb| from_lsb_to_mdps(lsb, fs)),
+        })
+    }
+
+    /// Get the acceleration in g as Q16.16 fixed point (65536 = 1 g), scaled
+    /// for the current full scale with integer math only.
+    #[cfg(feature = "integer_conversions")]
+    pub async fn acceleration_q16_get(&mut self) -> Result<[i32; 3], Error<B::Error>> {
+        let fs = self.xl_full_scale_get().await?;
+        let raw = self.acceleration_raw_get().await?;
+
+        Ok(raw.map(|lsb| from_lsb_to_g_q16(lsb, fs)))
+    }
+
+    /// Get the angular rate in dps as Q16.16 fixed point (65536 = 1 dps),
+    /// scaled for the current full scale with integer math only.
+    #[cfg(feature = "integer_conversions")]
+    pub async fn angular_rate_q16_get(&mut self) -> Result<[i32; 3], Error<B::Error>> {
+        let fs = self.gy_full_scale_get().await?;
+        let raw = self.angular_rate_raw_get().await?;
+
+        Ok(raw.map(|lsb| from_lsb_to_dps_q16(lsb, fs)))
+    }
+
+    /// Set the number of samples discarded by `enable_and_settle` and
+    /// `read_sample` after a sensor is powered up or its ODR changed
+    /// (default 1).
+    pub fn set_startup_discard(&mut self, n: u8) {
+        self.startup_discard = n;
+    }
+
+    /// Set the accelerometer and gyroscope ODRs and discard the first
+    /// samples of each enabled sensor, as configured with
+    /// `set_startup_discard`, so the next read returns settled data.
+    ///
+    /// Each discarded sample is waited for at most `DRDY_TIMEOUT_MS`,
+    /// otherwise `Error::Timeout` is returned.
+    pub async fn enable_and_settle(
+        &mut self,
+        xl: XlDataRate,
+        gy: GyDataRate,
+    ) -> Result<(), Error<B::Error>> {
+        self.xl_data_rate_set(xl).await?;
+        self.gy_data_rate_set(gy).await?;
+
+        for _ in 0..self.startup_discard {
+            if xl != XlDataRate::Off {
+                self.data_ready_wait(DataReadySource::Xl, DRDY_TIMEOUT_MS * 1000)
+                    .await?;
+                self.acceleration_raw_get().await?;
+            }
+            if gy != GyDataRate::Off {
+                self.data_ready_wait(DataReadySource::Gy, DRDY_TIMEOUT_MS * 1000)
+                    .await?;
+                self.angular_rate_raw_get().await?;
+            }
+        }
+        self.pending_discard = 0;
+
+        Ok(())
+    }
+
+    /// Check that the accelerometer output is changing.
+    ///
+    /// Reads `samples` data-ready gated samples and returns true if any of
+    /// them differs from the first one; a stuck or disconnected sensor keeps
+    /// returning the same value. Each sample is waited for at most three
+    /// periods of the current ODR; a sensor that stops signalling data-ready
+    /// is reported as not alive. The accelerometer must be running and
+    /// `samples` must be at least 2, otherwise `Error::UnexpectedValue` is
+    /// returned.
+    pub async fn accelerometer_liveness(&mut self, samples: u8) -> Result<bool, Error<B::Error>> {
+        if samples < 2 {
+            return Err(Error::UnexpectedValue);
+        }
+
+        let period_us =
+            xl_odr_period_us(self.xl_data_rate_get().await?).ok_or(Error::UnexpectedValue)?;
+        let timeout_us = 3 * period_us;
+
+        let mut first = None;
+        for _ in 0..samples {
+            match self.data_ready_wait(DataReadySource::Xl, timeout_us).await {
+                Ok(()) => {}
+                Err(Error::Timeout) => return Ok(false),
+                Err(e) => return Err(e),
+            }
+
+            let sample = self.acceleration_raw_get().await?;
+            match first {
+                None => first = Some(sample),
+                Some(first) if sample != first => return Ok(true),
+                Some(_) => {}
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Capture `N` consecutive accelerometer samples into a fixed-size array.
+    ///
+    /// Each sample is gated on the XLDA flag; if a sample does not arrive
+    /// within `DRDY_TIMEOUT_MS` the capture stops with `Error::Timeout`.
+    pub async fn read_xl_samples<const N: usize>(
+        &mut self,
+    ) -> Result<[[i16; 3]; N], Error<B::Error>> {
+        let mut out = [[0; 3]; N];
+        for sample in out.iter_mut() {
+            self.data_ready_wait(DataReadySource::Xl, DRDY_TIMEOUT_MS * 1000)
+                .await?;
+            *sample = self.acceleration_raw_get().await?;
+        }
+
+        Ok(out)
+    }
+
+    /// Stream accelerometer samples to `sink` until it returns `false`.
+    ///
+    /// Each sample is gated on XLDA, polled at the driver poll interval; if a
+    /// sample does not arrive within `DRDY_TIMEOUT_MS` streaming stops with
+    /// `Error::Timeout`. The caller decides duration and buffering.
+    pub async fn stream_xl(
+        &mut self,
+        mut sink: impl FnMut([i16; 3]) -> bool,
+    ) -> Result<(), Error<B::Error>> {
+        loop {
+            self.data_ready_wait(DataReadySource::Xl, DRDY_TIMEOUT_MS * 1000)
+                .await?;
+            let sample = self.acceleration_raw_get().await?;
+            if !sink(sample) {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Acquire data-ready gated accelerometer or gyroscope samples into
+    /// separate per-axis buffers.
+    ///
+    /// Fills as many samples as the shortest of `x`, `y` and `z` holds and
+    /// returns that count. `gated_on` selects the sensor (`Xl` or `Gy`);
+    /// `Temp` returns `Error::UnexpectedValue`. If a sample does not arrive
+    /// within `DRDY_TIMEOUT_MS` the acquisition stops with `Error::Timeout`.
+    pub async fn acquire_into_soa(
+        &mut self,
+        x: &mut [i16],
+        y: &mut [i16],
+        z: &mut [i16],
+        gated_on: DataReadySource,
+    ) -> Result<usize, Error<B::Error>> {
+        if gated_on == DataReadySource::Temp {
+            return Err(Error::UnexpectedValue);
+        }
+
+        let count = x.len().min(y.len()).min(z.len());
+        for i in 0..count {
+            self.data_ready_wait(gated_on, DRDY_TIMEOUT_MS * 1000)
+                .await?;
+            let sample = if gated_on == DataReadySource::Xl {
+                self.acceleration_raw_get().await?
+            } else {
+                self.angular_rate_raw_get().await?
+            };
+
+            x[i] = sample[0];
+            y[i] = sample[1];
+            z[i] = sample[2];
+        }
+
+        Ok(count)
+    }
+
+    /// Wait for new accelerometer, gyroscope and temperature data and read
+    /// them, converted with the configured full scales.
+    ///
+    /// The first call after an ODR change made through this driver first
+    /// discards the number of samples set with `set_startup_discard`.
+    ///
+    /// Each flag is waited for at most `DRDY_TIMEOUT_MS`: if either sensor
+    /// is powered down, `Error::Timeout` is returned.
+    pub async fn read_sample(&mut self) -> Result<Sample, Error<B::Error>> {
+        let xl_fs = self.xl_full_scale_get().await?;
+        let gy_fs = self.gy_full_scale_get().await?;
+
+        while self.pending_discard > 0 {
+            self.data_ready_wait(DataReadySource::Xl, DRDY_TIMEOUT_MS * 1000)
+                .await?;
+            self.acceleration_raw_get().await?;
+            self.data_ready_wait(DataReadySource::Gy, DRDY_TIMEOUT_MS * 1000)
+                .await?;
+            self.angular_rate_raw_get().await?;
+            self.pending_discard -= 1;
+        }
+
+        self.data_ready_wait(DataReadySource::Xl, DRDY_TIMEOUT_MS * 1000)
+            .await?;
+        self.data_ready_wait(DataReadySource::Gy, DRDY_TIMEOUT_MS * 1000)
+            .await?;
+        self.data_ready_wait(DataReadySource::Temp, DRDY_TIMEOUT_MS * 1000)
+            .await?;
+
+        let accel = self.acceleration_raw_get().await?;
+        let gyro = self.angular_rate_raw_get().await?;
+        let temp = self.temperature_raw_get().await?;
+
+        Ok(Sample {
+            accel_mg: accel.map(|lsb| from_lsb_to_mg(lsb, xl_fs)),
+            gyro_mdps: gyro.map(|lsb| from_lsb_to_mdps(lsb, gy_fs)),
+            temp_c: from_lsb_to_celsius(temp),
+        })
+    }
+
+    /// Read and drop the next `n` gyroscope samples, each gated on GDA and
+    /// bounded by `DRDY_TIMEOUT_MS`.
+    ///
+    /// This device has no DRDY_MASK bit to hold data-ready back during the
+    /// gyroscope start-up transient (CTRL4_C bit 3 is reserved), so the
+    /// first samples after power-up or wake-up have to be discarded in
+    /// software.
+    pub async fn gy_discard_samples(&mut self, n: u8) -> Result<(), Error<B::Error>> {
+        for _ in 0..n {
+            self.data_ready_wait(DataReadySource::Gy, DRDY_TIMEOUT_MS * 1000)
+                .await?;
+            self.angular_rate_raw_get().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Put the gyroscope in sleep mode.
+    ///
+    /// Unlike setting the gyroscope ODR to off, sleep mode keeps the drive
+    /// circuitry running, so resuming with `gy_resume_fast` takes a fraction
+    /// of the full power-up time at the cost of a higher current than
+    /// power-down. The configured ODR is kept.
+    pub async fn gy_standby(&mut self) -> Result<(), Error<B::Error>> {
+        self.sleep_set(Sleep::GyroDisable).await
+    }
+
+    /// Leave gyroscope sleep mode and wait `settle_ms`.
+    ///
+    /// The settle time needed after sleep is much shorter than after
+    /// switching the gyroscope on from power-down; see the turn-on times in
+    /// the datasheet. Use `gy_wake_and_read` to also discard the first
+    /// sample.
+    pub async fn gy_resume_fast(&mut self, settle_ms: u32) -> Result<(), Error<B::Error>> {
+        self.sleep_set(Sleep::GyroEnable).await?;
+        self.tim.delay_ms(settle_ms).await;
+
+        Ok(())
+    }
+
+    /// Wake the gyroscope from sleep mode and return its first valid sample.
+    ///
+    /// The samples produced right after leaving sleep are affected by the
+    /// start-up transient: this clears SLEEP_G, waits `settle_ms`, then
+    /// discards one sample and returns the next. Each sample is gated on GDA
+    /// and bounded by `DRDY_TIMEOUT_MS`. The gyroscope ODR must not be off.
+    pub async fn gy_wake_and_read(&mut self, settle_ms: u32) -> Result<[i16; 3], Error<B::Error>> {
+        self.sleep_set(Sleep::GyroEnable).await?;
+        self.tim.delay_ms(settle_ms).await;
+        self.gy_discard_samples(1).await?;
+
+        self.data_ready_wait(DataReadySource::Gy, DRDY_TIMEOUT_MS * 1000)
+            .await?;
+        self.angular_rate_raw_get().await
+    }
+
+    /// Read the current outputs in SI units, the usual input of sensor
+    /// fusion (AHRS) crates.
+    ///
+    /// Acceleration and angular rate are converted with the configured full
+    /// scales. `timestamp_s` is filled only when the timestamp counter is
+    /// enabled (25 us per LSB); being an f32, its resolution degrades as the
+    /// counter grows (about 8 ms near the 107374 s rollover). No data-ready
+    /// flag is waited for.
+    #[cfg(feature = "math")]
+    pub async fn imu_sample(&mut self) -> Result<ImuSample, Error<B::Error>> {
+        let accel_mps2 = self.acceleration_get(AccelUnit::MetersPerSec2).await?;
+        let gyro_rads = self.angular_rate_get(AngularUnit::RadPerSec).await?;
+        let temp_c = self.temperature_get(TempScale::Celsius).await?;
+
+        let timestamp_s = if self.timestamp_get().await? == 1 {
+            Some(self.timestamp_coherent_get().await? as f32 * 25e-6)
+        } else {
+            None
+        };
+
+        Ok(ImuSample {
+            accel_mps2,
+            gyro_rads,
+            temp_c,
+            timestamp_s,
+        })
+    }
+
+    /// Get the magnitude of the linear acceleration vector in mg, using the
+    /// current accelerometer full scale. No data-ready flag is waited for.
+    #[cfg(feature = "math")]
+    pub async fn acceleration_magnitude_mg(&mut self) -> Result<f32, Error<B::Error>> {
+        let [x, y, z] = self.acceleration_get(AccelUnit::Mg).await?;
+
+        Ok(libm::sqrtf(x * x + y * y + z * z))
+    }
+
+    /// Like `read_sample`, but refuses to read unless block data update is
+    /// enabled.
+    ///
+    /// Each output is split over a low and a high byte register: without BDU
+    /// the device may update one byte while the other is being read, giving
+    /// a torn value. Returns `Error::UnexpectedValue` if BDU is off.
+    pub async fn read_sample_bdu_safe(&mut self) -> Result<Sample, Error<B::Error>> {
+        if self.block_data_update_get().await? == 0 {
+            return Err(Error::UnexpectedValue);
+        }
+
+        self.read_sample().await
+    }
+
+    /// Get the angular rate converted to `unit` using the current gyroscope
+    /// full scale.
+    pub async fn angular_rate_get(
+        &mut self,
+        unit: AngularUnit,
+    ) -> Result<[f32; 3], Error<B::Error>> {
+        let fs = self.gy_full_scale_get().await?;
+        let raw = self.angular_rate_raw_get().await?;
+
+        Ok(raw.map(|lsb| unit.from_mdps(from_lsb_to_mdps(lsb, fs))))
+    }
+
+    /// Retrive INT1_CTRL register: data-ready and boot routing on INT1.
+    pub async fn int1_ctrl_get(&mut self) -> Result<Int1Ctrl, Error<B::Error>> {
+        Int1Ctrl::read(self).await
+    }
+
+    /// Retrive INT2_CTRL register: data-ready and ISPU sleep routing on INT2.
+    pub async fn int2_ctrl_get(&mut self) -> Result<Int2Ctrl, Error<B::Error>> {
+        Int2Ctrl::read(self).await
+    }
+
     /// It routes interrupt signals on INT 1 pin.
     pub async fn pin_int1_route_set(&mut self, val: PinInt1Route) -> Result<(), Error<B::Error>> {
         let mut int1_ctrl = Int1Ctrl::read(self).await?;
@@ -664,6 +1916,99 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
         Ok(val)
     }
 
+    /// Get the routing of both interrupt pins (INT1_CTRL, MD1_CFG, INT2_CTRL
+    /// and MD2_CFG).
+    pub async fn int_routes_get(
+        &mut self,
+    ) -> Result<(PinInt1Route, PinInt2Route), Error<B::Error>> {
+        let int1 = self.pin_int1_route_get().await?;
+        let int2 = self.pin_int2_route_get().await?;
+
+        Ok((int1, int2))
+    }
+
+    /// Route interrupt sources to INT1 and INT2 from two masks.
+    ///
+    /// Each enabled source is mapped to its bit in INT1_CTRL/MD1_CFG or
+    /// INT2_CTRL/MD2_CFG; disabled sources are unrouted. Not every source
+    /// exists on both pins: `drdy_temp` and `timestamp` are INT2-only,
+    /// `sh_endop` and `boot` are INT1-only. Requesting one on the other pin
+    /// returns `Error::UnexpectedValue` before anything is written. The
+    /// INT2 ISPU sleep signal, which has no mask field, is left unchanged.
+    pub async fn route_sources(
+        &mut self,
+        to_int1: SourceMask,
+        to_int2: SourceMask,
+    ) -> Result<(), Error<B::Error>> {
+        if to_int1.drdy_temp || to_int1.timestamp || to_int2.sh_endop || to_int2.boot {
+            return Err(Error::UnexpectedValue);
+        }
+
+        let int1 = PinInt1Route {
+            drdy_xl: to_int1.drdy_xl as u8,
+            drdy_gy: to_int1.drdy_gy as u8,
+            boot: to_int1.boot as u8,
+            sh_endop: to_int1.sh_endop as u8,
+            ispu: to_int1.ispu as u8,
+        };
+
+        let mut int2 = self.pin_int2_route_get().await?;
+        int2.drdy_xl = to_int2.drdy_xl as u8;
+        int2.drdy_gy = to_int2.drdy_gy as u8;
+        int2.drdy_temp = to_int2.drdy_temp as u8;
+        int2.timestamp = to_int2.timestamp as u8;
+        int2.ispu = to_int2.ispu as u8;
+
+        self.pin_int1_route_set(int1).await?;
+        self.pin_int2_route_set(int2).await
+    }
+
+    /// Mask every INT1 source, e.g. to quiesce a shared interrupt line
+    /// during a critical section.
+    ///
+    /// The current routing (INT1_CTRL and MD1_CFG) is saved in the driver
+    /// and restored by `int1_unmute`. Calling it again while muted keeps the
+    /// routing saved by the first call.
+    pub async fn int1_mute(&mut self) -> Result<(), Error<B::Error>> {
+        if self.int1_saved.is_none() {
+            let routes = self.pin_int1_route_get().await?;
+            self.pin_int1_route_set(PinInt1Route::default()).await?;
+            self.int1_saved = Some(routes);
+        }
+
+        Ok(())
+    }
+
+    /// Restore the INT1 routing saved by `int1_mute`. Does nothing if INT1
+    /// is not muted.
+    pub async fn int1_unmute(&mut self) -> Result<(), Error<B::Error>> {
+        if let Some(routes) = self.int1_saved {
+            self.pin_int1_route_set(routes).await?;
+            self.int1_saved = None;
+        }
+
+        Ok(())
+    }
+
+    /// Route the timestamp overflow alert (6.4 ms before rollover) on INT2.
+    ///
+    /// The alert is only generated while the timestamp counter is enabled:
+    /// enabling the routing with the counter off returns
+    /// `Error::UnexpectedValue` and leaves MD2_CFG untouched. Disabling is
+    /// always allowed.
+    pub async fn route_timestamp_overflow_int2(
+        &mut self,
+        enable: bool,
+    ) -> Result<(), Error<B::Error>> {
+        if enable && Ctrl10C::read(self).await?.timestamp_en() == 0 {
+            return Err(Error::UnexpectedValue);
+        }
+
+        let mut md2_cfg = Md2Cfg::read(self).await?;
+        md2_cfg.set_int2_timestamp(enable as u8);
+        md2_cfg.write(self).await
+    }
+
     /// Set Push-pull/open-drain on INT1 and INT2 pins.
     pub async fn int_pin_mode_set(&mut self, val: IntPinMode) -> Result<(), Error<B::Error>> {
         let mut ctrl3_c = Ctrl3C::read(self).await?;
@@ -697,10 +2042,60 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
     }
 
     /// Retrive the Sensor hub output data.
+    ///
+    /// `val` is filled starting from SENSOR_HUB_1; it may be at most
+    /// `SENSOR_HUB_REGISTER_COUNT` bytes long, otherwise
+    /// `Error::UnexpectedValue` is returned.
     pub async fn sh_read_data_raw_get(&mut self, val: &mut [u8]) -> Result<(), Error<B::Error>> {
+        if val.len() > SENSOR_HUB_REGISTER_COUNT {
+            return Err(Error::UnexpectedValue);
+        }
+
         self.operate_over_sensor_hub(async |lock| SensorHub1::read_more(lock, val).await)
             .await
     }
+
+    /// Retrive the Sensor hub output data split per external sensor.
+    ///
+    /// The hub window starting at SENSOR_HUB_1 is read once and its bytes are
+    /// handed out in order: `out[i]` receives the next `lengths[i]` bytes.
+    /// `lengths` and `out` must have the same number of entries, every
+    /// `out[i]` must hold at least `lengths[i]` bytes and the total must fit
+    /// in `SENSOR_HUB_REGISTER_COUNT`, otherwise `Error::UnexpectedValue` is
+    /// returned. The `crate::util` helpers decode the resulting payloads.
+    pub async fn sh_read_split(
+        &mut self,
+        lengths: &[u8],
+        out: &mut [&mut [u8]],
+    ) -> Result<(), Error<B::Error>> {
+        if lengths.len() != out.len() {
+            return Err(Error::UnexpectedValue);
+        }
+
+        let mut total = 0usize;
+        for (len, buf) in lengths.iter().zip(out.iter()) {
+            if buf.len() < *len as usize {
+                return Err(Error::UnexpectedValue);
+            }
+            total += *len as usize;
+        }
+        if total > SENSOR_HUB_REGISTER_COUNT {
+            return Err(Error::UnexpectedValue);
+        }
+
+        let mut raw = [0u8; SENSOR_HUB_REGISTER_COUNT];
+        self.sh_read_data_raw_get(&mut raw[..total]).await?;
+
+        let mut offset = 0usize;
+        for (len, buf) in lengths.iter().zip(out.iter_mut()) {
+            let len = *len as usize;
+            buf[..len].copy_from_slice(&raw[offset..offset + len]);
+            offset += len;
+        }
+
+        Ok(())
+    }
+
     /// Set the number of external sensors to be read by the sensor hub.
     pub async fn sh_slave_connected_set(
         &mut self,
@@ -846,6 +2241,21 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
         .await
     }
 
+    /// Tear down sensor hub operation and return to plain UI reads.
+    ///
+    /// Turns the I2C master off, pulses RST_MASTER_REGS to clear the master
+    /// logic and output registers, then makes sure the main bank is
+    /// selected. The target configuration must be written again before the
+    /// hub is re-enabled. The accelerometer ODR is left unchanged: if it was
+    /// only running as the hub trigger, power it down with
+    /// `xl_data_rate_set`.
+    pub async fn sh_disable(&mut self) -> Result<(), Error<B::Error>> {
+        self.sh_master_set(0).await?;
+        self.sh_reset_set(1).await?;
+        self.sh_reset_set(0).await?;
+        self.mem_bank_set(MemBank::MainMemBank).await
+    }
+
     /// Configure target 0 for perform a write.
     ///
     /// # Arguments
@@ -854,8 +2264,16 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
     ///     - `tgt0_add`: 8-bit I2C device address
     ///     - `tgt0_subadd`: 8-bit register device address
     ///     - `tgt0_data`: 8-bit data to write
+    ///
+    /// The target registers must not change while the I2C master is running
+    /// (MASTER_ON = 1), as a cycle in progress could use a half-written
+    /// configuration: in that case `Error::UnexpectedValue` is returned.
     pub async fn sh_cfg_write(&mut self, val: ShCfgWrite) -> Result<(), Error<B::Error>> {
         self.operate_over_sensor_hub(async |lock| {
+            if MasterConfig::read(lock).await?.master_on() == 1 {
+                return Err(Error::UnexpectedValue);
+            }
+
             let mut reg = Slv0Add::from_bits(0);
             reg.set_slave0_add(val.slv0_add);
             reg.set_rw_0(0);
@@ -867,24 +2285,154 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
         .await
     }
 
-    /// Set the rate at which the master communicates.
-    pub async fn sh_data_rate_set(&mut self, val: ShDataRate) -> Result<(), Error<B::Error>> {
-        self.operate_over_sensor_hub(async |lock| {
-            let mut slv0_config = Slv0Config::read(lock).await?;
-            slv0_config.set_shub_odr((val as u8) & 0x07);
-            slv0_config.write(lock).await
-        })
-        .await
+    /// Write one byte to an external sensor through the sensor hub master.
+    ///
+    /// Configures target 0 with `sh_cfg_write`, enables the I2C master and
+    /// uses the accelerometer as trigger, then waits for `sens_hub_endop`
+    /// for at most `SH_ENDOP_TIMEOUT_MS` (`Error::Timeout` otherwise).
+    /// The master is turned off and the accelerometer ODR restored before
+    /// returning, also when the wait times out or a step fails.
+    pub async fn sh_write_once(&mut self, cfg: ShCfgWrite) -> Result<(), Error<B::Error>> {
+        self.sh_cfg_write(cfg).await?;
+        self.sh_run_cycle().await.map(|_| ())
     }
 
-    /// Get the actual rate configuration at which the master communicates.
-    pub async fn sh_data_rate_get(&mut self) -> Result<ShDataRate, Error<B::Error>> {
-        self.operate_over_sensor_hub(async |lock| {
-            let slv0_config = Slv0Config::read(lock).await?;
-            let shub_odr = slv0_config.shub_odr();
+    /// Run one sensor hub cycle with the configured targets and report, per
+    /// target, whether it answered: the cycle completed (`sens_hub_endop`)
+    /// and the target did not NACK.
+    ///
+    /// Targets beyond the count set with `sh_slave_connected_set` are
+    /// reported as `false`, as are all of them if the cycle does not
+    /// complete within `SH_ENDOP_TIMEOUT_MS`. The accelerometer is used as
+    /// trigger; the master is turned off and the accelerometer ODR restored
+    /// afterwards.
+    pub async fn sh_probe_slaves(&mut self) -> Result<[bool; 4], Error<B::Error>> {
+        let connected = self.sh_slave_connected_get().await?.count() as usize;
+
+        let status = match self.sh_run_cycle().await {
+            Ok(status) => status,
+            Err(Error::Timeout) => return Ok([false; 4]),
+            Err(e) => return Err(e),
+        };
 
-            let val = ShDataRate::try_from(shub_odr).unwrap_or_default();
-            Ok(val)
+        let nack = [
+            status.slave0_nack(),
+            status.slave1_nack(),
+            status.slave2_nack(),
+            status.slave3_nack(),
+        ];
+        let mut answered = [false; 4];
+        for (i, flag) in answered.iter_mut().enumerate().take(connected) {
+            *flag = nack[i] == 0;
+        }
+
+        Ok(answered)
+    }
+
+    /// Enable the I2C master with the accelerometer as trigger and wait for
+    /// `sens_hub_endop` for at most `SH_ENDOP_TIMEOUT_MS`.
+    ///
+    /// Whatever the outcome, also when enabling the master fails, the master
+    /// is turned off again and the accelerometer ODR found on entry is
+    /// restored. The first error is returned.
+    async fn sh_run_cycle(&mut self) -> Result<StatusMaster, Error<B::Error>> {
+        let xl_odr = self.xl_data_rate_get().await?;
+
+        let result = self.sh_trigger_and_wait().await;
+
+        // Disable I2C master and restore XL (trigger).
+        let master_off = self.sh_master_set(0).await;
+        let restored = self.xl_data_rate_set(xl_odr).await;
+
+        let status = result?;
+        master_off?;
+        restored?;
+        Ok(status)
+    }
+
+    /// Start a sensor hub cycle and wait for it; `sh_run_cycle` cleans up.
+    async fn sh_trigger_and_wait(&mut self) -> Result<StatusMaster, Error<B::Error>> {
+        // Disable accelerometer
+        self.xl_data_rate_set(XlDataRate::Off).await?;
+        // Enable I2C Master
+        self.sh_master_set(1).await?;
+        // Enable accelerometer to trigger Sensor Hub operation.
+        self.xl_data_rate_set(XlDataRate::_26hzHp).await?;
+
+        self.sh_endop_poll(SH_ENDOP_TIMEOUT_MS, 1000).await
+    }
+
+    /// Wait for `sens_hub_endop` for at most `timeout_ms`, polling every
+    /// millisecond.
+    ///
+    /// If the flag does not set in time, e.g. because an external slave
+    /// holds the bus, the I2C master is disabled so the device is not left
+    /// stuck in a sensor hub cycle, and `Error::Timeout` is returned. All the
+    /// driver's sensor hub transfers wait through this.
+    pub async fn sh_wait_endop(&mut self, timeout_ms: u32) -> Result<(), Error<B::Error>> {
+        self.sh_endop_poll(timeout_ms, 1000).await.map(|_| ())
+    }
+
+    /// Poll STATUS_MASTER_MAINPAGE every `step_us` until `sens_hub_endop` is
+    /// set, and return the status. On timeout the I2C master is disabled and
+    /// `Error::Timeout` is returned.
+    ///
+    /// With a `step_us` of 0 the flag is polled back to back, without any
+    /// delay, as many times as the driver poll interval would allow.
+    async fn sh_endop_poll(
+        &mut self,
+        timeout_ms: u32,
+        step_us: u32,
+    ) -> Result<StatusMaster, Error<B::Error>> {
+        let timeout_us = timeout_ms.saturating_mul(1000);
+        let counted_us = match step_us {
+            0 => self.poll_interval_us,
+            step_us => step_us,
+        };
+
+        let mut waited: u32 = 0;
+        loop {
+            let status = self.sh_status_get().await?;
+            if status.sens_hub_endop() == 1 {
+                return Ok(status);
+            }
+            if waited >= timeout_us {
+                let _ = self.sh_master_set(0).await;
+                return Err(Error::Timeout);
+            }
+            if step_us > 0 {
+                self.tim.delay_us(step_us).await;
+            }
+            waited = waited.saturating_add(counted_us);
+        }
+    }
+
+    /// Set the rate at which the master communicates.
+    ///
+    /// SHUB_ODR is a 2-bit field; a value that does not fit is rejected with
+    /// `Error::UnexpectedValue` instead of being silently truncated.
+    pub async fn sh_data_rate_set(&mut self, val: ShDataRate) -> Result<(), Error<B::Error>> {
+        let bits = val.as_bits();
+        if bits & !ShDataRate::FIELD_MASK != 0 {
+            return Err(Error::UnexpectedValue);
+        }
+
+        self.operate_over_sensor_hub(async |lock| {
+            let mut slv0_config = Slv0Config::read(lock).await?;
+            slv0_config.set_shub_odr(bits);
+            slv0_config.write(lock).await
+        })
+        .await
+    }
+
+    /// Get the actual rate configuration at which the master communicates.
+    pub async fn sh_data_rate_get(&mut self) -> Result<ShDataRate, Error<B::Error>> {
+        self.operate_over_sensor_hub(async |lock| {
+            let slv0_config = Slv0Config::read(lock).await?;
+            let shub_odr = slv0_config.shub_odr();
+
+            let val = ShDataRate::try_from(shub_odr).unwrap_or_default();
+            Ok(val)
         })
         .await
     }
@@ -898,11 +2446,19 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
     ///     - `slv_add`: 8-bit I2C device address
     ///     - `slv_subadd`: 8-bit register device address
     ///     - `slv_len`: Number of bits to read
+    ///
+    /// The target registers must not change while the I2C master is running
+    /// (MASTER_ON = 1), as a cycle in progress could use a half-written
+    /// configuration: in that case `Error::UnexpectedValue` is returned.
     pub async fn sh_slv_cfg_read(
         &mut self,
         idx: u8,
         val: &ShCfgRead,
     ) -> Result<(), Error<B::Error>> {
+        if self.sh_master_get().await? == 1 {
+            return Err(Error::UnexpectedValue);
+        }
+
         let mut slv_add = Slv0Add::from_bits(0);
 
         self.mem_bank_set(MemBank::SensorHubMemBank).await?;
@@ -954,6 +2510,11 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
         Ok(StatusMaster::from_bits(value.into()))
     }
 
+    /// Retrive the raw STATUS_MASTER_MAINPAGE byte, e.g. for logging.
+    pub async fn sh_status_raw_get(&mut self) -> Result<u8, Error<B::Error>> {
+        StatusMasterMainpage::read(self).await.map(|reg| reg.into())
+    }
+
     /// Enable/Disable the software reset of ISPU core.
     pub async fn ispu_reset_set(&mut self, val: u8) -> Result<(), Error<B::Error>> {
         let mut func_cfg_access = FuncCfgAccess::read(self).await?;
@@ -985,6 +2546,21 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
         Ok(val)
     }
 
+    /// Get the ISPU core clock (CTRL10_C) and output data rate (CTRL9_C).
+    ///
+    /// The ISPU program runs once per ISPU ODR period, so the clock cycles
+    /// available to the algorithm per sample are the core clock divided by
+    /// the rate: e.g. 5 MHz at 104 Hz leaves about 48000 cycles, 10 MHz at
+    /// 833 Hz about 12000.
+    pub async fn ispu_timing_get(
+        &mut self,
+    ) -> Result<(IspuClockSel, IspuDataRate), Error<B::Error>> {
+        let clock = self.ispu_clock_get().await?;
+        let rate = self.ispu_data_rate_get().await?;
+
+        Ok((clock, rate))
+    }
+
     /// Set the ISPU output data rate (ODR).
     pub async fn ispu_data_rate_set(&mut self, val: IspuDataRate) -> Result<(), Error<B::Error>> {
         let mut ctrl9_c = Ctrl9C::read(self).await?;
@@ -1002,6 +2578,55 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
         Ok(val)
     }
 
+    /// Get the actual ISPU ODR, failing on an undefined ISPU_RATE encoding
+    /// instead of reporting `Off`.
+    pub async fn ispu_data_rate_get_strict(&mut self) -> Result<IspuDataRate, Error<B::Error>> {
+        let ispu_rate = Ctrl9C::read(self).await.map(|reg| reg.ispu_rate())?;
+
+        IspuDataRate::try_from(ispu_rate).map_err(|_| Error::UnexpectedValue)
+    }
+
+    /// Raise the accelerometer ODR, if needed, so that it is not lower than
+    /// the ISPU output data rate.
+    ///
+    /// The accelerometer power mode is kept: a low-power ODR is bumped to
+    /// the matching low-power ODR. Nothing is written when the ISPU is off
+    /// or the accelerometer is already fast enough.
+    pub async fn ensure_xl_odr_for_ispu(&mut self) -> Result<(), Error<B::Error>> {
+        let ispu = self.ispu_data_rate_get().await?;
+        let xl = self.xl_data_rate_get().await?;
+        if xl_odr_feeds_ispu(xl, ispu) {
+            return Ok(());
+        }
+
+        let min = min_xl_odr_for_ispu(ispu) as u8 | (xl as u8 & 0x10);
+        let val = XlDataRate::try_from(min).map_err(|_| Error::UnexpectedValue)?;
+        self.xl_data_rate_set(val).await
+    }
+
+    /// Set up the accelerometer to drive an ISPU motion algorithm.
+    ///
+    /// Sets the accelerometer ODR and the ISPU rate, then routes the ISPU
+    /// interrupt to INT1 (MD1_CFG.INT1_ISPU), leaving the other INT1 sources
+    /// unchanged. Returns `Error::UnexpectedValue`, without writing
+    /// anything, if `xl_odr` is slower than `ispu_rate`.
+    pub async fn configure_ispu_motion_trigger(
+        &mut self,
+        xl_odr: XlDataRate,
+        ispu_rate: IspuDataRate,
+    ) -> Result<(), Error<B::Error>> {
+        if !xl_odr_feeds_ispu(xl_odr, ispu_rate) {
+            return Err(Error::UnexpectedValue);
+        }
+
+        self.xl_data_rate_set(xl_odr).await?;
+        self.ispu_data_rate_set(ispu_rate).await?;
+
+        let mut int1 = self.pin_int1_route_get().await?;
+        int1.ispu = 1;
+        self.pin_int1_route_set(int1).await
+    }
+
     /// Configure the ISPU BDU mode.
     pub async fn ispu_bdu_set(&mut self, val: IspuBdu) -> Result<(), Error<B::Error>> {
         let mut ctrl9_c = Ctrl9C::read(self).await?;
@@ -1013,9 +2638,29 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
 
     /// Get the actual ISPU BDU mode.
     pub async fn ispu_bdu_get(&mut self) -> Result<IspuBdu, Error<B::Error>> {
-        let ispu_rate = Ctrl9C::read(self).await.map(|reg| reg.ispu_rate())?;
+        let ispu_bdu = Ctrl9C::read(self).await.map(|reg| reg.ispu_bdu())?;
+
+        Ok(IspuBdu::try_from(ispu_bdu).unwrap_or_default())
+    }
 
-        Ok(IspuBdu::try_from(ispu_rate).unwrap_or_default())
+    /// Retrive CTRL9_C register: ISPU BDU mode and ISPU rate.
+    pub async fn ctrl9c_get(&mut self) -> Result<Ctrl9C, Error<B::Error>> {
+        Ctrl9C::read(self).await
+    }
+
+    /// Retrive the raw CTRL9_C byte.
+    pub async fn ctrl9c_raw_get(&mut self) -> Result<u8, Error<B::Error>> {
+        Ctrl9C::read(self).await.map(|reg| reg.into())
+    }
+
+    /// Retrive CTRL10_C register: ISPU clock selection and timestamp enable.
+    pub async fn ctrl10c_get(&mut self) -> Result<Ctrl10C, Error<B::Error>> {
+        Ctrl10C::read(self).await
+    }
+
+    /// Retrive the raw CTRL10_C byte.
+    pub async fn ctrl10c_raw_get(&mut self) -> Result<u8, Error<B::Error>> {
+        Ctrl10C::read(self).await.map(|reg| reg.into())
     }
 
     /// Retrive IspuIntStatusMainPage: Generic Interrupt Flags from ISPU.
@@ -1023,6 +2668,17 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
         IspuIntStatusMainPage::read(self).await.map(|reg| reg.0)
     }
 
+    /// Check whether the ISPU published new output data.
+    ///
+    /// The device has no dedicated DOUT data-ready bit: an ISPU program
+    /// signals new output by raising its interrupt flag in
+    /// ISPU_INT_STATUS0..3_MAINPAGE (one bit per algorithm) after writing
+    /// DOUT. Returns `true` when any of those 32 bits is set. The flags are
+    /// cleared by the ISPU program, not by this read.
+    pub async fn ispu_data_ready(&mut self) -> Result<bool, Error<B::Error>> {
+        Ok(self.ia_ispu_get().await? != 0)
+    }
+
     /// General purpose input write configuration register for ISPU.
     ///
     /// # Arguments
@@ -1132,6 +2788,37 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
         .await
     }
 
+    /// Returns true once the ISPU boot procedure has ended (BOOT_END).
+    pub async fn ispu_boot_complete(&mut self) -> Result<bool, Error<B::Error>> {
+        self.operate_over_ispu(async |lock| {
+            IspuStatus::read(lock).await.map(|reg| reg.boot_end() == 1)
+        })
+        .await
+    }
+
+    /// Reset the ISPU core and wait for it to boot again.
+    ///
+    /// Sets SW_RESET_ISPU, clears it (the bit is not self-clearing), then
+    /// polls BOOT_END every millisecond for at most `timeout_ms`, returning
+    /// `Error::Timeout` if the ISPU has not booted by then.
+    pub async fn ispu_reset_sequence(&mut self, timeout_ms: u32) -> Result<(), Error<B::Error>> {
+        self.ispu_reset_set(1).await?;
+        self.ispu_reset_set(0).await?;
+
+        for _ in 0..timeout_ms {
+            if self.ispu_boot_complete().await? {
+                return Ok(());
+            }
+            self.tim.delay_ms(1).await;
+        }
+
+        if self.ispu_boot_complete().await? {
+            Ok(())
+        } else {
+            Err(Error::Timeout)
+        }
+    }
+
     /// ISPU write memory.
     ///
     /// ISPU clock is disabled inside the routine.
@@ -1165,28 +2852,9 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
             ispu_mem_sel.write(lock).await?;
 
             if mem_sel == IspuMemoryType::ProgramRamMemory {
-                let mut addr_s = [0u16; 4];
-                let mut len_s = [0u16; 4];
-                let mut j = 0;
-                let mut k = 0;
+                let (addr_s, len_s, j) = ispu_program_ram_segments(mem_addr, len);
 
-                addr_s[0] = mem_addr;
-                for i in 0..len {
-                    if (mem_addr + i == 0x2000)
-                        || (mem_addr + i == 0x4000)
-                        || (mem_addr + i == 0x6000)
-                    {
-                        len_s[j] = k;
-                        j += 1;
-                        addr_s[j] = mem_addr + i;
-                        k = 0;
-                    }
-                    k += 1;
-                }
-                len_s[j] = k;
-                j += 1;
-
-                k = 0;
+                let mut k = 0;
                 for i in 0..j {
                     lock.ispu_sel_memory_addr(addr_s[i]).await?;
                     lock.write_to_register(
@@ -1214,7 +2882,8 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
 
     /// ISPU read memory.
     ///
-    /// ISPU clock is disabled inside the routine.
+    /// ISPU clock is disabled inside the routine. READ_MEM_EN is cleared and
+    /// the clock restored before returning, also when the read fails.
     ///
     /// # Arguments
     ///
@@ -1241,22 +2910,128 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
             // Select memory to be read
             ispu_mem_sel.set_read_mem_en(1);
             ispu_mem_sel.set_mem_sel(mem_sel as u8);
-            ispu_mem_sel.write(lock).await?;
+            let mut result = ispu_mem_sel.write(lock).await;
 
             // Select memory address
-            lock.ispu_sel_memory_addr(mem_addr).await?;
+            if result.is_ok() {
+                result = lock.ispu_sel_memory_addr(mem_addr).await;
+            }
 
             // Read data
-            let _dummy = IspuMemData::read(lock).await;
-            IspuMemData::read_more(lock, &mut mem_data[0..len.into()]).await?;
+            if result.is_ok() {
+                let _dummy = IspuMemData::read(lock).await;
+                result = IspuMemData::read_more(lock, &mut mem_data[0..len.into()]).await;
+            }
+
+            // Leave read mode whatever the outcome of the read
+            ispu_mem_sel.set_read_mem_en(0);
+            let read_mem_off = ispu_mem_sel.write(lock).await;
 
             // Set ISPU clock back to previous value
             ispu_cfg.set_clk_dis(clk_dis);
-            ispu_cfg.write(lock).await
+            let clk_restored = ispu_cfg.write(lock).await;
+
+            result?;
+            read_mem_off?;
+            clk_restored
         })
         .await
     }
 
+    /// Get the ISPU memory selection: selected memory and READ_MEM_EN.
+    ///
+    /// After `ispu_write_memory` and `ispu_read_memory` READ_MEM_EN is 0; a
+    /// 1 here means a memory access was interrupted.
+    pub async fn ispu_mem_sel_get(&mut self) -> Result<(IspuMemoryType, bool), Error<B::Error>> {
+        let ispu_mem_sel = self.operate_over_ispu(IspuMemSel::read).await?;
+
+        let mem = IspuMemoryType::try_from(ispu_mem_sel.mem_sel()).unwrap_or_default();
+        Ok((mem, ispu_mem_sel.read_mem_en() == 1))
+    }
+
+    /// ISPU write memory and read it back to verify the content.
+    ///
+    /// The data is written with `ispu_write_memory` and then read back in
+    /// chunks of 32 bytes; the first mismatch returns `Error::UnexpectedValue`.
+    /// An image that would run past address 0xFFFF is rejected with
+    /// `Error::UnexpectedValue` before anything is written.
+    ///
+    /// # Arguments
+    ///
+    /// * `mem_sel`: IspuMemoryType
+    /// * `mem_addr`: Memory address
+    /// * `mem_data`: Memory data
+    pub async fn ispu_load_and_verify(
+        &mut self,
+        mem_sel: IspuMemoryType,
+        mem_addr: u16,
+        mem_data: &[u8],
+    ) -> Result<(), Error<B::Error>> {
+        let len = u16::try_from(mem_data.len()).map_err(|_| Error::UnexpectedValue)?;
+        mem_addr.checked_add(len).ok_or(Error::UnexpectedValue)?;
+        self.ispu_write_memory(mem_sel, mem_addr, mem_data, len)
+            .await?;
+
+        let mut buf = [0u8; 32];
+        let mut addr = mem_addr;
+        for chunk in mem_data.chunks(buf.len()) {
+            let read = &mut buf[..chunk.len()];
+            self.ispu_read_memory(mem_sel, addr, read, chunk.len() as u16)
+                .await?;
+            if read != chunk {
+                return Err(Error::UnexpectedValue);
+            }
+            addr = addr
+                .checked_add(chunk.len() as u16)
+                .ok_or(Error::UnexpectedValue)?;
+        }
+
+        Ok(())
+    }
+
+    /// ISPU read memory in pieces of at most `chunk.len()` bytes.
+    ///
+    /// Reads `total_len` bytes starting at `mem_addr`, calling `on_chunk`
+    /// with the offset from `mem_addr` and the bytes read for each piece.
+    /// Returns `Error::UnexpectedValue`, before accessing the bus, if `chunk`
+    /// is empty or the range runs past the 16-bit address space.
+    ///
+    /// # Arguments
+    ///
+    /// * `mem_sel`: IspuMemoryType
+    /// * `mem_addr`: Memory address
+    /// * `total_len`: Number of bytes to read
+    /// * `chunk`: Scratch buffer, its length sets the piece size
+    /// * `on_chunk`: Called as `on_chunk(offset, data)` for each piece
+    pub async fn ispu_read_memory_chunked(
+        &mut self,
+        mem_sel: IspuMemoryType,
+        mem_addr: u16,
+        total_len: u16,
+        chunk: &mut [u8],
+        mut on_chunk: impl FnMut(u16, &[u8]),
+    ) -> Result<(), Error<B::Error>> {
+        if chunk.is_empty() {
+            return Err(Error::UnexpectedValue);
+        }
+        mem_addr
+            .checked_add(total_len)
+            .ok_or(Error::UnexpectedValue)?;
+
+        let chunk_len = u16::try_from(chunk.len()).unwrap_or(u16::MAX);
+        let mut offset = 0;
+        while offset < total_len {
+            let len = chunk_len.min(total_len - offset);
+            let data = &mut chunk[..len as usize];
+            let addr = mem_addr.checked_add(offset).ok_or(Error::UnexpectedValue)?;
+            self.ispu_read_memory(mem_sel, addr, data, len).await?;
+            on_chunk(offset, data);
+            offset += len;
+        }
+
+        Ok(())
+    }
+
     /// ISPU write flags (IF2S)
     pub async fn ispu_write_flags(&mut self, data: u16) -> Result<(), Error<B::Error>> {
         self.operate_over_ispu(async |lock| IspuIf2sFlag(data).write(lock).await)
@@ -1269,6 +3044,16 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
             .await
     }
 
+    /// ISPU read flags (S2IF), decoded.
+    pub async fn ispu_s2if_flags(&mut self) -> Result<IspuFlags, Error<B::Error>> {
+        self.ispu_read_flags().await.map(IspuFlags)
+    }
+
+    /// ISPU write flags (IF2S) from a decoded flag set.
+    pub async fn ispu_if2s_flags_set(&mut self, flags: IspuFlags) -> Result<(), Error<B::Error>> {
+        self.ispu_write_flags(flags.0).await
+    }
+
     /// ISPU clear flags (S2IF)
     pub async fn ispu_clear_flags(&mut self) -> Result<(), Error<B::Error>> {
         self.operate_over_ispu(async |lock| IspuS2ifFlagH::from_bits(1).write(lock).await)
@@ -1287,6 +3072,51 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
             .await
     }
 
+    /// Retrive ISPU DOUT registers as signed 16-bit words.
+    ///
+    /// Reads `out.len()` little-endian words starting from DOUT_`start_word`
+    /// (0 to 31). Returns `Error::UnexpectedValue` if the range exceeds the
+    /// 32 output words.
+    pub async fn ispu_read_dout_i16(
+        &mut self,
+        start_word: u8,
+        out: &mut [i16],
+    ) -> Result<(), Error<B::Error>> {
+        if start_word as usize + out.len() > 32 {
+            return Err(Error::UnexpectedValue);
+        }
+
+        let mut buf = [0u8; 64];
+        let bytes = &mut buf[..out.len() * 2];
+        self.operate_over_ispu(async |lock| {
+            lock.read_from_register(IspuReg::IspuDout00L as u8 + start_word * 2, bytes)
+                .await
+        })
+        .await?;
+
+        for (word, chunk) in out.iter_mut().zip(bytes.chunks_exact(2)) {
+            *word = i16::from_le_bytes([chunk[0], chunk[1]]);
+        }
+
+        Ok(())
+    }
+
+    /// Retrive all the 32 ISPU DOUT words (DOUT_00 to DOUT_31).
+    ///
+    /// The 64 bytes are read in a single burst with one ISPU bank switch.
+    pub async fn ispu_read_all_dout(&mut self) -> Result<[u16; 32], Error<B::Error>> {
+        let mut buf = [0u8; 64];
+        self.operate_over_ispu(async |lock| IspuDout00L::read_more(lock, &mut buf).await)
+            .await?;
+
+        let mut out = [0u16; 32];
+        for (word, chunk) in out.iter_mut().zip(buf.chunks_exact(2)) {
+            *word = u16::from_le_bytes([chunk[0], chunk[1]]);
+        }
+
+        Ok(out)
+    }
+
     /// Get the ISPU int1_ctrl configuration.
     ///
     /// Each bit is a flag to route interrupt on INT1. INT1_ISPU must be also set to 1.
@@ -1319,6 +3149,64 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
             .await
     }
 
+    /// Route (`on = true`) or unroute ISPU interrupt `idx` (0 to 29) on INT1,
+    /// leaving the other flags of ISPU_INT1_CTRL unchanged.
+    ///
+    /// Returns `Error::UnexpectedValue` if `idx` is 30 or more.
+    pub async fn ispu_int1_route_bit(&mut self, idx: u8, on: bool) -> Result<(), Error<B::Error>> {
+        if idx >= 30 {
+            return Err(Error::UnexpectedValue);
+        }
+
+        let mut val = self.ispu_int1_ctrl_get().await?;
+        if on {
+            val |= 1 << idx;
+        } else {
+            val &= !(1 << idx);
+        }
+        self.ispu_int1_ctrl_set(val).await
+    }
+
+    /// Returns true if ISPU interrupt `idx` (0 to 29) is routed on INT1.
+    ///
+    /// Returns `Error::UnexpectedValue` if `idx` is 30 or more.
+    pub async fn ispu_int1_route_is_set(&mut self, idx: u8) -> Result<bool, Error<B::Error>> {
+        if idx >= 30 {
+            return Err(Error::UnexpectedValue);
+        }
+
+        Ok(self.ispu_int1_ctrl_get().await? & (1 << idx) != 0)
+    }
+
+    /// Route (`on = true`) or unroute ISPU interrupt `idx` (0 to 29) on INT2,
+    /// leaving the other flags of ISPU_INT2_CTRL unchanged.
+    ///
+    /// Returns `Error::UnexpectedValue` if `idx` is 30 or more.
+    pub async fn ispu_int2_route_bit(&mut self, idx: u8, on: bool) -> Result<(), Error<B::Error>> {
+        if idx >= 30 {
+            return Err(Error::UnexpectedValue);
+        }
+
+        let mut val = self.ispu_int2_ctrl_get().await?;
+        if on {
+            val |= 1 << idx;
+        } else {
+            val &= !(1 << idx);
+        }
+        self.ispu_int2_ctrl_set(val).await
+    }
+
+    /// Returns true if ISPU interrupt `idx` (0 to 29) is routed on INT2.
+    ///
+    /// Returns `Error::UnexpectedValue` if `idx` is 30 or more.
+    pub async fn ispu_int2_route_is_set(&mut self, idx: u8) -> Result<bool, Error<B::Error>> {
+        if idx >= 30 {
+            return Err(Error::UnexpectedValue);
+        }
+
+        Ok(self.ispu_int2_ctrl_get().await? & (1 << idx) != 0)
+    }
+
     /// Retrive ISPU int_status.
     ///
     /// Get the actual 30 bit interrupt configuration.
@@ -1336,6 +3224,16 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
             .await
     }
 
+    /// Retrive the ISPU algorithm enables as one flag per algorithm.
+    ///
+    /// Index `n` is bit `n` of ISPU_ALGO; bits 30 and 31 are unused and not
+    /// reported.
+    pub async fn ispu_algo_enabled_mask(&mut self) -> Result<[bool; 30], Error<B::Error>> {
+        let algo = self.ispu_algo_get().await?;
+
+        Ok(core::array::from_fn(|n| algo & (1 << n) != 0))
+    }
+
     /// Set ISPU algo: each bit enables the corresponding algorithm.
     ///
     /// Enable configurations in order to run up to 30 independent algorithms.
@@ -1345,34 +3243,278 @@ impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
         self.operate_over_ispu(async |lock| algo.write(lock).await)
             .await
     }
-}
 
-#[bisync]
-pub fn from_fs2g_to_mg(lsb: i16) -> f32 {
-    (lsb as f32) * 0.061
-}
+    /// Configure the ISPU algorithms after the program has been loaded.
+    ///
+    /// Writes `ISPU_ALGO`, `ISPU_INT1_CTRL`, `ISPU_INT2_CTRL` and the latched
+    /// interrupt configuration while switching to the ISPU bank only once.
+    ///
+    /// # Arguments
+    ///
+    /// * `algo_mask`: Algorithms to enable (one bit per algorithm).
+    /// * `int1_mask`: Algorithm interrupts routed on INT1. INT1_ISPU must be also set to 1.
+    /// * `int2_mask`: Algorithm interrupts routed on INT2. INT2_ISPU must be also set to 1.
+    /// * `latched`: IspuInterrupt mode.
+    pub async fn ispu_setup(
+        &mut self,
+        algo_mask: u32,
+        int1_mask: u32,
+        int2_mask: u32,
+        latched: IspuInterrupt,
+    ) -> Result<(), Error<B::Error>> {
+        self.operate_over_ispu(async |lock| {
+            IspuAlgo(algo_mask).write(lock).await?;
+            IspuInt1Ctrl(int1_mask).write(lock).await?;
+            IspuInt2Ctrl(int2_mask).write(lock).await?;
 
-#[bisync]
-pub fn from_fs4g_to_mg(lsb: i16) -> f32 {
-    (lsb as f32) * 0.122
+            let mut ispu_config = IspuConfig::read(lock).await?;
+            ispu_config.set_latched((latched as u8) & 0x1);
+            ispu_config.write(lock).await
+        })
+        .await
+    }
 }
 
+/// Split an ISPU program RAM access at the 0x2000, 0x4000 and 0x6000
+/// boundaries, where the memory address must be programmed again.
+///
+/// Returns the start address and length of each segment and the number of
+/// segments.
 #[bisync]
-pub fn from_fs8g_to_mg(lsb: i16) -> f32 {
-    (lsb as f32) * 0.244
-}
+fn ispu_program_ram_segments(mem_addr: u16, len: u16) -> ([u16; 4], [u16; 4], usize) {
+    let mut addr_s = [0u16; 4];
+    let mut len_s = [0u16; 4];
+    let mut j = 0;
+    let mut k = 0;
+
+    addr_s[0] = mem_addr;
+    for i in 0..len {
+        let addr = mem_addr + i;
+        if k != 0 && (addr == 0x2000 || addr == 0x4000 || addr == 0x6000) {
+            len_s[j] = k;
+            j += 1;
+            addr_s[j] = addr;
+            k = 0;
+        }
+        k += 1;
+    }
+    len_s[j] = k;
+    j += 1;
 
-#[bisync]
-pub fn from_fs16g_to_mg(lsb: i16) -> f32 {
-    (lsb as f32) * 0.488
+    (addr_s, len_s, j)
 }
 
-#[bisync]
-pub fn from_fs125dps_to_mdps(lsb: i16) -> f32 {
-    (lsb as f32) * 4.375
-}
+#[only_async]
+impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
+    /// Average `n` samples returned by `read`.
+    ///
+    /// Before each call to `read` the data-ready flag of `gated_on` is polled,
+    /// so every sample is a new one, for at most `DRDY_TIMEOUT_MS`: if the
+    /// sensor is off, `Error::Timeout` is returned. Returns
+    /// `Error::UnexpectedValue` if `n` is 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `n`: Number of samples to average.
+    /// * `gated_on`: Sensor whose data-ready flag gates each read.
+    /// * `read`: Reads one raw sample, e.g. `acceleration_raw_get`.
+    pub async fn average_samples<F>(
+        &mut self,
+        n: usize,
+        gated_on: DataReadySource,
+        mut read: F,
+    ) -> Result<[f32; 3], Error<B::Error>>
+    where
+        F: AsyncFnMut(&mut Self) -> Result<[i16; 3], Error<B::Error>>,
+    {
+        if n == 0 {
+            return Err(Error::UnexpectedValue);
+        }
 
-#[bisync]
+        let mut sum = [0f32; 3];
+        for _ in 0..n {
+            self.data_ready_wait(gated_on, DRDY_TIMEOUT_MS * 1000)
+                .await?;
+            let sample = read(self).await?;
+            for (acc, val) in sum.iter_mut().zip(sample) {
+                *acc += val as f32;
+            }
+        }
+
+        Ok(sum.map(|acc| acc / n as f32))
+    }
+
+    /// Run `f` with the accelerometer temporarily set to `odr`.
+    ///
+    /// The current ODR is saved and restored after `f` returns, also when
+    /// setting `odr` or `f` fails. The first error is returned.
+    pub async fn with_temporary_xl_odr<F, R>(
+        &mut self,
+        odr: XlDataRate,
+        f: F,
+    ) -> Result<R, Error<B::Error>>
+    where
+        F: AsyncFnOnce(&mut Self) -> Result<R, Error<B::Error>>,
+    {
+        let saved = self.xl_data_rate_get().await?;
+
+        let result = match self.xl_data_rate_set(odr).await {
+            Ok(()) => f(self).await,
+            Err(e) => Err(e),
+        };
+        let restored = self.xl_data_rate_set(saved).await;
+
+        let val = result?;
+        restored?;
+        Ok(val)
+    }
+
+    /// Run `f` with the accelerometer and gyroscope in low-power mode.
+    ///
+    /// The current ODRs are saved, both sensors are switched to the
+    /// low-power ODR with the same rate (sensors that are off stay off),
+    /// and the saved ODRs, with their power mode, are restored after `f`
+    /// returns, also when switching or `f` fails. The first error is
+    /// returned.
+    pub async fn with_low_power<F, R>(&mut self, f: F) -> Result<R, Error<B::Error>>
+    where
+        F: AsyncFnOnce(&mut Self) -> Result<R, Error<B::Error>>,
+    {
+        let xl = self.xl_data_rate_get().await?;
+        let gy = self.gy_data_rate_get().await?;
+
+        let result = match self.low_power_odrs_set(xl, gy).await {
+            Ok(()) => f(self).await,
+            Err(e) => Err(e),
+        };
+        let restored_xl = self.xl_data_rate_set(xl).await;
+        let restored_gy = self.gy_data_rate_set(gy).await;
+
+        let val = result?;
+        restored_xl?;
+        restored_gy?;
+        Ok(val)
+    }
+}
+
+#[only_sync]
+impl<B: BusOperation, T: DelayNs> Lsm6dso16is<B, T, MainBank> {
+    /// Average `n` samples returned by `read`.
+    ///
+    /// Before each call to `read` the data-ready flag of `gated_on` is polled,
+    /// so every sample is a new one, for at most `DRDY_TIMEOUT_MS`: if the
+    /// sensor is off, `Error::Timeout` is returned. Returns
+    /// `Error::UnexpectedValue` if `n` is 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `n`: Number of samples to average.
+    /// * `gated_on`: Sensor whose data-ready flag gates each read.
+    /// * `read`: Reads one raw sample, e.g. `acceleration_raw_get`.
+    pub fn average_samples<F>(
+        &mut self,
+        n: usize,
+        gated_on: DataReadySource,
+        mut read: F,
+    ) -> Result<[f32; 3], Error<B::Error>>
+    where
+        F: FnMut(&mut Self) -> Result<[i16; 3], Error<B::Error>>,
+    {
+        if n == 0 {
+            return Err(Error::UnexpectedValue);
+        }
+
+        let mut sum = [0f32; 3];
+        for _ in 0..n {
+            self.data_ready_wait(gated_on, DRDY_TIMEOUT_MS * 1000)?;
+            let sample = read(self)?;
+            for (acc, val) in sum.iter_mut().zip(sample) {
+                *acc += val as f32;
+            }
+        }
+
+        Ok(sum.map(|acc| acc / n as f32))
+    }
+
+    /// Run `f` with the accelerometer temporarily set to `odr`.
+    ///
+    /// The current ODR is saved and restored after `f` returns, also when
+    /// setting `odr` or `f` fails. The first error is returned.
+    pub fn with_temporary_xl_odr<F, R>(
+        &mut self,
+        odr: XlDataRate,
+        f: F,
+    ) -> Result<R, Error<B::Error>>
+    where
+        F: FnOnce(&mut Self) -> Result<R, Error<B::Error>>,
+    {
+        let saved = self.xl_data_rate_get()?;
+
+        let result = match self.xl_data_rate_set(odr) {
+            Ok(()) => f(self),
+            Err(e) => Err(e),
+        };
+        let restored = self.xl_data_rate_set(saved);
+
+        let val = result?;
+        restored?;
+        Ok(val)
+    }
+
+    /// Run `f` with the accelerometer and gyroscope in low-power mode.
+    ///
+    /// The current ODRs are saved, both sensors are switched to the
+    /// low-power ODR with the same rate (sensors that are off stay off),
+    /// and the saved ODRs, with their power mode, are restored after `f`
+    /// returns, also when switching or `f` fails. The first error is
+    /// returned.
+    pub fn with_low_power<F, R>(&mut self, f: F) -> Result<R, Error<B::Error>>
+    where
+        F: FnOnce(&mut Self) -> Result<R, Error<B::Error>>,
+    {
+        let xl = self.xl_data_rate_get()?;
+        let gy = self.gy_data_rate_get()?;
+
+        let result = match self.low_power_odrs_set(xl, gy) {
+            Ok(()) => f(self),
+            Err(e) => Err(e),
+        };
+        let restored_xl = self.xl_data_rate_set(xl);
+        let restored_gy = self.gy_data_rate_set(gy);
+
+        let val = result?;
+        restored_xl?;
+        restored_gy?;
+        Ok(val)
+    }
+}
+
+#[bisync]
+pub fn from_fs2g_to_mg(lsb: i16) -> f32 {
+    (lsb as f32) * 0.061
+}
+
+#[bisync]
+pub fn from_fs4g_to_mg(lsb: i16) -> f32 {
+    (lsb as f32) * 0.122
+}
+
+#[bisync]
+pub fn from_fs8g_to_mg(lsb: i16) -> f32 {
+    (lsb as f32) * 0.244
+}
+
+#[bisync]
+pub fn from_fs16g_to_mg(lsb: i16) -> f32 {
+    (lsb as f32) * 0.488
+}
+
+#[bisync]
+pub fn from_fs125dps_to_mdps(lsb: i16) -> f32 {
+    (lsb as f32) * 4.375
+}
+
+#[bisync]
 pub fn from_fs250dps_to_mdps(lsb: i16) -> f32 {
     (lsb as f32) * 8.75
 }
@@ -1397,11 +3539,175 @@ pub fn from_lsb_to_celsius(lsb: i16) -> f32 {
     (lsb as f32 / 256.0) + 25.0
 }
 
+/// Convert an acceleration sample to mg for the given full scale.
+#[bisync]
+pub fn from_lsb_to_mg(lsb: i16, fs: XlFullScale) -> f32 {
+    match fs {
+        XlFullScale::_2g => from_fs2g_to_mg(lsb),
+        XlFullScale::_4g => from_fs4g_to_mg(lsb),
+        XlFullScale::_8g => from_fs8g_to_mg(lsb),
+        XlFullScale::_16g => from_fs16g_to_mg(lsb),
+    }
+}
+
+/// Convert an angular rate sample to mdps for the given full scale.
+#[bisync]
+pub fn from_lsb_to_mdps(lsb: i16, fs: GyFullScale) -> f32 {
+    match fs {
+        GyFullScale::_125dps => from_fs125dps_to_mdps(lsb),
+        GyFullScale::_250dps => from_fs250dps_to_mdps(lsb),
+        GyFullScale::_500dps => from_fs500dps_to_mdps(lsb),
+        GyFullScale::_1000dps => from_fs1000dps_to_mdps(lsb),
+        GyFullScale::_2000dps => from_fs2000dps_to_mdps(lsb),
+    }
+}
+
+/// Accelerometer self-test output change limits (min, max) in mg, from the
+/// datasheet, for the given full scale.
+///
+/// The datasheet only specifies ±4 g, the scale used by its self-test
+/// procedure (at ±2 g the deflection added to gravity can saturate the
+/// output); `None` is returned for the other full scales.
+#[bisync]
+pub fn xl_self_test_range_mg(fs: XlFullScale) -> Option<(f32, f32)> {
+    match fs {
+        XlFullScale::_4g => Some((50.0, 1700.0)),
+        _ => None,
+    }
+}
+
+/// Gyroscope self-test output change limits (min, max) in mdps, from the
+/// datasheet, for the given full scale.
+///
+/// The datasheet only specifies ±250 dps and ±2000 dps; `None` is returned
+/// for the other full scales.
+#[bisync]
+pub fn gy_self_test_range_mdps(fs: GyFullScale) -> Option<(f32, f32)> {
+    match fs {
+        GyFullScale::_250dps => Some((20_000.0, 80_000.0)),
+        GyFullScale::_2000dps => Some((150_000.0, 700_000.0)),
+        _ => None,
+    }
+}
+
+/// Convert a block of raw acceleration samples to mg for the given full
+/// scale. Converts as many samples as the shorter of `raw` and `out` holds.
+#[bisync]
+pub fn convert_xl_block(raw: &[[i16; 3]], fs: XlFullScale, out: &mut [[f32; 3]]) {
+    for (mg, sample) in out.iter_mut().zip(raw) {
+        *mg = sample.map(|lsb| from_lsb_to_mg(lsb, fs));
+    }
+}
+
+/// Convert a block of raw angular rate samples to mdps for the given full
+/// scale. Converts as many samples as the shorter of `raw` and `out` holds.
+#[bisync]
+pub fn convert_gy_block(raw: &[[i16; 3]], fs: GyFullScale, out: &mut [[f32; 3]]) {
+    for (mdps, sample) in out.iter_mut().zip(raw) {
+        *mdps = sample.map(|lsb| from_lsb_to_mdps(lsb, fs));
+    }
+}
+
+/// Convert an acceleration sample to g in Q16.16 fixed point (65536 = 1 g)
+/// for the given full scale, using integer math only.
+///
+/// The full ±16 g range fits comfortably in the i32 result; the value is
+/// rounded toward zero (at most 1/65536 g off the float path).
+#[cfg(feature = "integer_conversions")]
+#[bisync]
+pub fn from_lsb_to_g_q16(lsb: i16, fs: XlFullScale) -> i32 {
+    // Sensitivity in ug/LSB
+    let ug_per_lsb: i64 = match fs {
+        XlFullScale::_2g => 61,
+        XlFullScale::_4g => 122,
+        XlFullScale::_8g => 244,
+        XlFullScale::_16g => 488,
+    };
+
+    (lsb as i64 * ug_per_lsb * 65536 / 1_000_000) as i32
+}
+
+/// Convert an angular rate sample to dps in Q16.16 fixed point
+/// (65536 = 1 dps) for the given full scale, using integer math only.
+///
+/// The full ±2000 dps range fits in the i32 result; the value is rounded
+/// toward zero (at most 1/65536 dps off the float path).
+#[cfg(feature = "integer_conversions")]
+#[bisync]
+pub fn from_lsb_to_dps_q16(lsb: i16, fs: GyFullScale) -> i32 {
+    // Sensitivity in udps/LSB
+    let udps_per_lsb: i64 = match fs {
+        GyFullScale::_125dps => 4375,
+        GyFullScale::_250dps => 8750,
+        GyFullScale::_500dps => 17500,
+        GyFullScale::_1000dps => 35000,
+        GyFullScale::_2000dps => 70000,
+    };
+
+    (lsb as i64 * udps_per_lsb * 65536 / 1_000_000) as i32
+}
+
+/// True if the accelerometer running at `xl` is at least as fast as the
+/// ISPU running at `ispu`.
+#[bisync]
+fn xl_odr_feeds_ispu(xl: XlDataRate, ispu: IspuDataRate) -> bool {
+    // Low nibble orders the rates; 1.6 Hz (0x1B) is below 12.5 Hz.
+    let xl_rate = match xl {
+        XlDataRate::_1_6hzLp => 0,
+        _ => xl as u8 & 0x0F,
+    };
+
+    xl_rate >= ispu as u8
+}
+
+/// Sample period, in microseconds, of the accelerometer running at `odr`;
+/// `None` when it is powered down.
+#[bisync]
+fn xl_odr_period_us(odr: XlDataRate) -> Option<u32> {
+    // Low nibble selects the rate, the high one only the power mode.
+    match odr as u8 & 0x0f {
+        0x1 => Some(80_000),
+        0x2 => Some(38_462),
+        0x3 => Some(19_231),
+        0x4 => Some(9_616),
+        0x5 => Some(4_808),
+        0x6 => Some(2_404),
+        0x7 => Some(1_201),
+        0x8 => Some(600),
+        0x9 => Some(300),
+        0xa => Some(150),
+        0xb => Some(625_000),
+        _ => None,
+    }
+}
+
+/// Slowest high-performance accelerometer ODR that can feed the ISPU at
+/// the given rate (the accelerometer must run at least as fast as the ISPU).
+#[bisync]
+pub fn min_xl_odr_for_ispu(ispu: IspuDataRate) -> XlDataRate {
+    match ispu {
+        IspuDataRate::Off => XlDataRate::Off,
+        IspuDataRate::_12_5hz => XlDataRate::_12_5hzHp,
+        IspuDataRate::_26hz => XlDataRate::_26hzHp,
+        IspuDataRate::_52hz => XlDataRate::_52hzHp,
+        IspuDataRate::_104hz => XlDataRate::_104hzHp,
+        IspuDataRate::_208hz => XlDataRate::_208hzHp,
+        IspuDataRate::_416hz => XlDataRate::_416hzHp,
+        IspuDataRate::_833hz => XlDataRate::_833hzHp,
+        IspuDataRate::_1667hz => XlDataRate::_1667hzHp,
+        IspuDataRate::_3333hz => XlDataRate::_3333hzHp,
+        IspuDataRate::_6667hz => XlDataRate::_6667hzHp,
+    }
+}
+
 #[cfg(feature = "passthrough")]
 /// Lsm6dso16isPassthrough
 ///
 /// Encapsulate the sensor as a bus that could be used inside another sensor
 /// to provide passthrough capability
+///
+/// A read returns 1 to 7 bytes (the range of SLAVE0_NUMOP); other lengths
+/// are rejected with `Error::UnexpectedValue` before the bus is accessed.
 pub struct Lsm6dso16isPassthrough<'a, B, T>
 where
     B: BusOperation,
@@ -1409,6 +3715,8 @@ where
 {
     sensor: &'a mut Lsm6dso16is<B, T, MainBank>,
     slave_address: SevenBitAddress,
+    poll_ms: u32,
+    settle_ms: u32,
 }
 
 #[cfg(feature = "passthrough")]
@@ -1424,8 +3732,23 @@ where
         Lsm6dso16isPassthrough {
             sensor,
             slave_address,
+            poll_ms: PASSTHROUGH_POLL_MS,
+            settle_ms: PASSTHROUGH_SETTLE_MS,
         }
     }
+
+    /// Override the delays used while waiting for the sensor hub, for both
+    /// reads and writes: `settle_ms` between data-ready polls of the
+    /// accelerometer trigger, `poll_ms` between end-of-operation polls.
+    ///
+    /// Zero disables the delay: the flag is then polled back to back. The
+    /// waits stay bounded by `DRDY_TIMEOUT_MS` and `SH_ENDOP_TIMEOUT_MS`
+    /// (counted in polls at the sensor poll interval when there is no delay).
+    pub fn with_delays(mut self, poll_ms: u32, settle_ms: u32) -> Self {
+        self.poll_ms = poll_ms;
+        self.settle_ms = settle_ms;
+        self
+    }
 }
 
 #[cfg(feature = "passthrough")]
@@ -1443,6 +3766,41 @@ where
     }
 
     async fn write_bytes(&mut self, wbuf: &[u8]) -> Result<(), Self::Error> {
+        let result = self.write_sequence(wbuf).await;
+        if result.is_err() {
+            self.release_master().await;
+        }
+
+        result
+    }
+
+    async fn write_byte_read_bytes(
+        &mut self,
+        wbuf: &[u8; 1],
+        rbuf: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        // SLAVE0_NUMOP is 3 bits wide: a single read moves 1 to 7 bytes.
+        if rbuf.is_empty() || rbuf.len() > 7 {
+            return Err(Error::UnexpectedValue);
+        }
+
+        let result = self.read_sequence(wbuf, rbuf).await;
+        if result.is_err() {
+            self.release_master().await;
+        }
+
+        result
+    }
+}
+
+#[cfg(feature = "passthrough")]
+#[bisync]
+impl<B, T> Lsm6dso16isPassthrough<'_, B, T>
+where
+    B: BusOperation,
+    T: DelayNs,
+{
+    async fn write_sequence(&mut self, wbuf: &[u8]) -> Result<(), Error<B::Error>> {
         let master = &mut self.sensor;
         let mut sh_cfg_write = ShCfgWrite::default();
 
@@ -1462,17 +3820,16 @@ where
             // Wait Sensor Hub operation flag set.
             let _dummy = master.acceleration_raw_get().await;
 
-            let mut drdy = 0;
-            while drdy == 0 {
-                master.tim.delay_ms(20).await;
-                drdy = master.xl_flag_data_ready_get().await?;
-            }
-
-            let mut end_op = 0;
-            while end_op == 0 {
-                master.tim.delay_ms(20).await;
-                end_op = master.sh_status_get().await?.sens_hub_endop();
-            }
+            master
+                .data_ready_poll(
+                    DataReadySource::Xl,
+                    DRDY_TIMEOUT_MS * 1000,
+                    self.settle_ms.saturating_mul(1000),
+                )
+                .await?;
+            master
+                .sh_endop_poll(SH_ENDOP_TIMEOUT_MS, self.poll_ms.saturating_mul(1000))
+                .await?;
 
             // Disable I2C master and XL (triger).
             master.sh_master_set(0).await?;
@@ -1482,11 +3839,11 @@ where
         Ok(())
     }
 
-    async fn write_byte_read_bytes(
+    async fn read_sequence(
         &mut self,
         wbuf: &[u8; 1],
         rbuf: &mut [u8],
-    ) -> Result<(), Self::Error> {
+    ) -> Result<(), Error<B::Error>> {
         let master = &mut self.sensor;
         // Disable accelerometer
         master.xl_data_rate_set(XlDataRate::Off).await?;
@@ -1505,17 +3862,16 @@ where
         // Wait Sensor Hub operation flag set
         let _dummy = master.acceleration_raw_get().await?;
 
-        let mut drdy = 0;
-        while drdy == 0 {
-            master.tim.delay_ms(20).await;
-            drdy = master.xl_flag_data_ready_get().await?;
-        }
-
-        let mut end_op = 0;
-        while end_op == 0 {
-            //master.tim.delay_ms(20);
-            end_op = master.sh_status_get().await?.sens_hub_endop();
-        }
+        master
+            .data_ready_poll(
+                DataReadySource::Xl,
+                DRDY_TIMEOUT_MS * 1000,
+                self.settle_ms.saturating_mul(1000),
+            )
+            .await?;
+        master
+            .sh_endop_poll(SH_ENDOP_TIMEOUT_MS, self.poll_ms.saturating_mul(1000))
+            .await?;
 
         // Disable I2C master and XL(trigger)
         master.sh_master_set(0).await?;
@@ -1526,16 +3882,79 @@ where
 
         Ok(())
     }
+
+    /// Best-effort cleanup after a failed transfer: disable the I2C master
+    /// and the accelerometer used as trigger, so that a transient bus error
+    /// does not leave the hub running. Errors are ignored, the caller
+    /// reports the original one.
+    async fn release_master(&mut self) {
+        let _ = self.sensor.sh_master_set(0).await;
+        let _ = self.sensor.xl_data_rate_set(XlDataRate::Off).await;
+    }
+}
+
+#[cfg(feature = "passthrough")]
+#[only_async]
+impl<B: BusOperation, T: DelayNs> Lsm6dso16isPassthrough<'_, B, T> {
+    /// Run `f` with direct access to the sensor acting as master.
+    ///
+    /// The passthrough holds the only mutable borrow of the master sensor,
+    /// so master configuration between slave operations has to go through
+    /// it. The borrow handed to `f` is exclusive and ends when `f` returns;
+    /// since every passthrough transfer releases the master before
+    /// returning, no sensor hub operation is in flight while `f` runs.
+    pub async fn with<F, R>(&mut self, f: F) -> R
+    where
+        F: AsyncFnOnce(&mut Lsm6dso16is<B, T, MainBank>) -> R,
+    {
+        f(self.sensor).await
+    }
+}
+
+#[cfg(feature = "passthrough")]
+#[only_sync]
+impl<B: BusOperation, T: DelayNs> Lsm6dso16isPassthrough<'_, B, T> {
+    /// Run `f` with direct access to the sensor acting as master.
+    ///
+    /// The passthrough holds the only mutable borrow of the master sensor,
+    /// so master configuration between slave operations has to go through
+    /// it. The borrow handed to `f` is exclusive and ends when `f` returns;
+    /// since every passthrough transfer releases the master before
+    /// returning, no sensor hub operation is in flight while `f` runs.
+    pub fn with<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut Lsm6dso16is<B, T, MainBank>) -> R,
+    {
+        f(self.sensor)
+    }
+}
+
+/// Interface used to reach the sensor, as reported by `BusKind`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[bisync]
+pub enum Interface {
+    /// I2C bus
+    I2c,
+    /// SPI bus
+    Spi,
 }
 
 #[repr(u8)]
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, TryFrom)]
+#[try_from(repr)]
 #[bisync]
 pub enum I2CAddress {
     I2cAddL = 0x6A,
     I2cAddH = 0x6B,
 }
 
+#[bisync]
+impl From<I2CAddress> for u8 {
+    fn from(address: I2CAddress) -> Self {
+        address as u8
+    }
+}
+
 #[derive(Clone, Copy, PartialEq)]
 #[bisync]
 pub struct AllSources {
@@ -1551,5 +3970,419 @@ pub struct AllSources {
     pub ispu: u32,
 }
 
+/// Interrupt sources to route to a pin with `route_sources`.
+#[derive(Clone, Copy, PartialEq, Default)]
+#[bisync]
+pub struct SourceMask {
+    pub drdy_xl: bool,
+    pub drdy_gy: bool,
+    /// INT2 only.
+    pub drdy_temp: bool,
+    /// INT1 only.
+    pub sh_endop: bool,
+    /// Timestamp overflow alert, INT2 only.
+    pub timestamp: bool,
+    pub ispu: bool,
+    /// INT1 only.
+    pub boot: bool,
+}
+
+/// Sensor configuration applied by `config_apply`.
+///
+/// Can be built fluently:
+/// `Config::new().xl(odr, fs).gy(odr, fs).bdu(true).int1_drdy_xl().build()`.
+#[derive(Clone, Copy, PartialEq, Default)]
+#[bisync]
+pub struct Config {
+    pub xl_odr: XlDataRate,
+    pub xl_fs: XlFullScale,
+    pub gy_odr: GyDataRate,
+    pub gy_fs: GyFullScale,
+    /// Block data update.
+    pub bdu: bool,
+    pub int1: PinInt1Route,
+}
+
+#[bisync]
+impl Config {
+    /// Start from the device defaults: both sensors off, no INT1 routing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the accelerometer ODR and full scale.
+    pub fn xl(mut self, odr: XlDataRate, fs: XlFullScale) -> Self {
+        self.xl_odr = odr;
+        self.xl_fs = fs;
+        self
+    }
+
+    /// Set the gyroscope ODR and full scale.
+    pub fn gy(mut self, odr: GyDataRate, fs: GyFullScale) -> Self {
+        self.gy_odr = odr;
+        self.gy_fs = fs;
+        self
+    }
+
+    /// Enable or disable block data update.
+    pub fn bdu(mut self, enable: bool) -> Self {
+        self.bdu = enable;
+        self
+    }
+
+    /// Route accelerometer data-ready on INT1.
+    pub fn int1_drdy_xl(mut self) -> Self {
+        self.int1.drdy_xl = 1;
+        self
+    }
+
+    /// Route gyroscope data-ready on INT1.
+    pub fn int1_drdy_gy(mut self) -> Self {
+        self.int1.drdy_gy = 1;
+        self
+    }
+
+    /// Finish the configuration.
+    pub fn build(self) -> Self {
+        self
+    }
+}
+
+/// Accelerometer sample returned by `acceleration_full`.
+#[derive(Clone, Copy, PartialEq)]
+#[bisync]
+pub struct AccelSample {
+    /// Raw output counts (X, Y, Z).
+    pub raw: [i16; 3],
+    /// Acceleration in mg (X, Y, Z).
+    pub mg: [f32; 3],
+}
+
+/// Gyroscope sample returned by `angular_rate_full`.
+#[derive(Clone, Copy, PartialEq)]
+#[bisync]
+pub struct GyroSample {
+    /// Raw output counts (X, Y, Z).
+    pub raw: [i16; 3],
+    /// Angular rate in mdps (X, Y, Z).
+    pub mdps: [f32; 3],
+}
+
+/// Converted sample set returned by `read_sample`.
+#[derive(Clone, Copy, PartialEq)]
+#[bisync]
+pub struct Sample {
+    /// Acceleration in mg (X, Y, Z).
+    pub accel_mg: [f32; 3],
+    /// Angular rate in mdps (X, Y, Z).
+    pub gyro_mdps: [f32; 3],
+    /// Temperature in °C.
+    pub temp_c: f32,
+}
+
+/// Sample in SI units returned by `imu_sample`.
+#[cfg(feature = "math")]
+#[derive(Clone, Copy, PartialEq)]
+#[bisync]
+pub struct ImuSample {
+    /// Acceleration in m/s² (X, Y, Z).
+    pub accel_mps2: [f32; 3],
+    /// Angular rate in rad/s (X, Y, Z).
+    pub gyro_rads: [f32; 3],
+    /// Temperature in °C.
+    pub temp_c: f32,
+    /// Timestamp in seconds, if the timestamp counter is enabled.
+    pub timestamp_s: Option<f32>,
+}
+
+/// One-shot snapshot of the device state returned by `status_summary`.
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[bisync]
+pub struct StatusSummary {
+    /// WHO_AM_I content, `ID` on a working part.
+    pub who_am_i: u8,
+    pub xl_odr: XlDataRate,
+    pub xl_fs: XlFullScale,
+    pub gy_odr: GyDataRate,
+    pub gy_fs: GyFullScale,
+    /// Block data update enabled.
+    pub bdu: bool,
+    /// New accelerometer data available.
+    pub xl_drdy: bool,
+    /// New gyroscope data available.
+    pub gy_drdy: bool,
+    /// New temperature data available.
+    pub temp_drdy: bool,
+}
+
+/// Sensor whose data-ready flag gates a read.
+#[derive(Clone, Copy, PartialEq)]
+#[bisync]
+pub enum DataReadySource {
+    /// Accelerometer (XLDA).
+    Xl,
+    /// Gyroscope (GDA).
+    Gy,
+    /// Temperature sensor (TDA).
+    Temp,
+}
+
+/// Sequence run by `reinitialize`.
+#[derive(Clone, Copy, PartialEq)]
+#[bisync]
+pub enum ReinitMode {
+    /// Restore the default value of the user registers (CTRL3_C.SW_RESET).
+    SoftwareReset,
+    /// Reload the trimming parameters from non-volatile memory
+    /// (CTRL3_C.BOOT); user registers keep their value.
+    RebootMemory,
+    /// Reboot memory, then software reset.
+    Both,
+}
+
+/// Unit of the acceleration returned by `acceleration_get`.
+#[derive(Clone, Copy, PartialEq)]
+#[bisync]
+pub enum AccelUnit {
+    /// Milli-g.
+    Mg,
+    /// Standard gravity.
+    G,
+    /// Meters per second squared.
+    MetersPerSec2,
+}
+
+#[bisync]
+impl AccelUnit {
+    /// Convert a value in mg to this unit.
+    pub fn from_mg(&self, mg: f32) -> f32 {
+        match self {
+            AccelUnit::Mg => mg,
+            AccelUnit::G => mg / 1000.0,
+            AccelUnit::MetersPerSec2 => mg / 1000.0 * 9.80665,
+        }
+    }
+}
+
+/// Unit of the angular rate returned by `angular_rate_get`.
+#[derive(Clone, Copy, PartialEq)]
+#[bisync]
+pub enum AngularUnit {
+    /// Milli-degrees per second.
+    Mdps,
+    /// Degrees per second.
+    Dps,
+    /// Radians per second.
+    RadPerSec,
+}
+
+#[bisync]
+impl AngularUnit {
+    /// Convert a value in mdps to this unit.
+    pub fn from_mdps(&self, mdps: f32) -> f32 {
+        match self {
+            AngularUnit::Mdps => mdps,
+            AngularUnit::Dps => mdps / 1000.0,
+            AngularUnit::RadPerSec => mdps / 1000.0 * core::f32::consts::PI / 180.0,
+        }
+    }
+}
+
+/// Scale of the temperature returned by `temperature_get`.
+#[derive(Clone, Copy, PartialEq)]
+#[bisync]
+pub enum TempScale {
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+}
+
+#[bisync]
+impl TempScale {
+    /// Convert a value in °C to this scale.
+    pub fn from_celsius(&self, celsius: f32) -> f32 {
+        match self {
+            TempScale::Celsius => celsius,
+            TempScale::Fahrenheit => celsius * 9.0 / 5.0 + 32.0,
+            TempScale::Kelvin => celsius + 273.15,
+        }
+    }
+}
+
+/// The 16 handshake flags exchanged with the ISPU program, host to ISPU
+/// (IF2S) or ISPU to host (S2IF).
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[bisync]
+pub struct IspuFlags(pub u16);
+
+#[bisync]
+impl IspuFlags {
+    /// Returns true if flag `n` (0 to 15) is set; false for `n` above 15.
+    pub fn get(&self, n: u8) -> bool {
+        n < 16 && self.0 & (1 << n) != 0
+    }
+
+    /// Set or clear flag `n` (0 to 15); `n` above 15 is ignored.
+    pub fn set(&mut self, n: u8, on: bool) {
+        if n >= 16 {
+            return;
+        }
+
+        if on {
+            self.0 |= 1 << n;
+        } else {
+            self.0 &= !(1 << n);
+        }
+    }
+}
+
+/// Type of a value in the ISPU output (DOUT) area, stored little-endian.
+#[derive(Clone, Copy, PartialEq)]
+#[bisync]
+pub enum IspuOutputType {
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+}
+
+#[bisync]
+impl IspuOutputType {
+    /// Size of the value in bytes.
+    pub fn size(&self) -> usize {
+        match self {
+            IspuOutputType::I16 | IspuOutputType::U16 => 2,
+            IspuOutputType::I32 | IspuOutputType::U32 | IspuOutputType::F32 => 4,
+        }
+    }
+}
+
+/// Value decoded from the ISPU output area by `IspuOutputReader`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[bisync]
+pub enum IspuValue {
+    I16(i16),
+    U16(u16),
+    I32(i32),
+    U32(u32),
+    F32(f32),
+}
+
+/// One field of an ISPU output layout.
+#[derive(Clone, Copy, PartialEq)]
+#[bisync]
+pub struct IspuOutputField {
+    /// Byte offset from ISPU_DOUT_00_L (0 to 63).
+    pub offset: u8,
+    pub ty: IspuOutputType,
+}
+
+/// Reader for an ISPU program with a fixed output layout.
+///
+/// Built once from the layout of the algorithm outputs: e.g. an algorithm
+/// writing x, y, z as i16 followed by their norm as f32 is described by
+/// `I16` fields at offsets 0, 2 and 4 and an `F32` field at offset 6.
+///
+/// Each `read` fetches the bytes covered by the layout in one burst and
+/// decodes them.
+#[derive(Clone, Copy)]
+#[bisync]
+pub struct IspuOutputReader<'a> {
+    layout: &'a [IspuOutputField],
+}
+
+#[bisync]
+impl<'a> IspuOutputReader<'a> {
+    pub fn new(layout: &'a [IspuOutputField]) -> Self {
+        Self { layout }
+    }
+
+    /// Read the outputs and decode them into `out`, one value per layout
+    /// field (as many as the shorter of the two holds).
+    ///
+    /// Returns `Error::UnexpectedValue` if a field extends past DOUT_31_H.
+    pub async fn read<B, T>(
+        &self,
+        sensor: &mut Lsm6dso16is<B, T, MainBank>,
+        out: &mut [IspuValue],
+    ) -> Result<(), Error<B::Error>>
+    where
+        B: BusOperation,
+        T: DelayNs,
+    {
+        let len = self
+            .layout
+            .iter()
+            .map(|field| field.offset as usize + field.ty.size())
+            .max()
+            .unwrap_or(0);
+        if len > 64 {
+            return Err(Error::UnexpectedValue);
+        }
+
+        let mut buf = [0u8; 64];
+        sensor.ispu_read_data_raw_get(&mut buf, len).await?;
+
+        for (value, field) in out.iter_mut().zip(self.layout) {
+            let b = &buf[field.offset as usize..];
+            *value = match field.ty {
+                IspuOutputType::I16 => IspuValue::I16(i16::from_le_bytes([b[0], b[1]])),
+                IspuOutputType::U16 => IspuValue::U16(u16::from_le_bytes([b[0], b[1]])),
+                IspuOutputType::I32 => IspuValue::I32(i32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+                IspuOutputType::U32 => IspuValue::U32(u32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+                IspuOutputType::F32 => IspuValue::F32(f32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+            };
+        }
+
+        Ok(())
+    }
+}
+
 #[bisync]
 pub const ID: u8 = 0x22;
+
+/// Default delay between two reads of a status flag in the driver polling
+/// loops; see `set_poll_interval_us`.
+#[bisync]
+pub const POLL_INTERVAL_US: u32 = 1000;
+
+/// Longest wait, in milliseconds, for the SW_RESET bit to clear.
+#[bisync]
+pub const SW_RESET_TIMEOUT_MS: u32 = 10;
+
+/// Longest wait, in milliseconds, for a sensor hub operation to complete.
+///
+/// At the slowest trigger used by the driver (26 Hz accelerometer) one
+/// sensor hub cycle takes about 40 ms.
+#[bisync]
+pub const SH_ENDOP_TIMEOUT_MS: u32 = 100;
+
+/// Number of reads attempted by `timestamp_coherent_get` before giving up
+/// on a coherent timestamp.
+#[bisync]
+pub const TIMESTAMP_READ_ATTEMPTS: u32 = 3;
+
+/// Longest wait, in milliseconds, for the BOOT bit to clear after the
+/// initial 10 ms of a memory reboot.
+#[bisync]
+pub const REBOOT_TIMEOUT_MS: u32 = 50;
+
+/// Longest wait, in milliseconds, for a single data-ready flag.
+///
+/// Covers one period at the slowest output data rate (1.6 Hz, 625 ms).
+#[bisync]
+pub const DRDY_TIMEOUT_MS: u32 = 1000;
+
+/// Default delay, in milliseconds, between two end-of-operation polls of a
+/// passthrough transfer (see `Lsm6dso16isPassthrough::with_delays`).
+#[cfg(feature = "passthrough")]
+#[bisync]
+pub const PASSTHROUGH_POLL_MS: u32 = 20;
+
+/// Default delay, in milliseconds, between two data-ready polls of the
+/// accelerometer trigger in a passthrough transfer.
+#[cfg(feature = "passthrough")]
+#[bisync]
+pub const PASSTHROUGH_SETTLE_MS: u32 = 20;
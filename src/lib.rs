@@ -2,6 +2,8 @@
 #![doc = include_str!("../README.md")]
 #![allow(clippy::duplicate_mod)]
 
+pub mod util;
+
 #[cfg(feature = "async")]
 #[path = "."]
 pub mod asynchronous {
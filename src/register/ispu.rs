@@ -1,8 +1,11 @@
 use super::super::{
     BusOperation, DelayNs, Error, Lsm6dso16is, RegisterOperation, SensorOperation, bisync,
-    register::IspuBank,
+    register::{IspuBank, MemBank, RegisterBank, register_bank},
 };
 
+#[cfg(feature = "debug_asserts")]
+use super::{ReservedBits, not_used};
+
 use bitfield_struct::bitfield;
 use derive_more::TryFrom;
 use st_mem_bank_macro::register;
@@ -1073,6 +1076,43 @@ pub struct IspuIntStatus3 {
 #[register(address = IspuReg::IspuAlgo0, access_type = "Lsm6dso16is<B, T, IspuBank>")]
 pub struct IspuAlgo(pub u32);
 
+/// Reserved bits of the writable registers, checked by the `debug_asserts` feature.
+#[cfg(feature = "debug_asserts")]
+pub(crate) const RESERVED_BITS: &[ReservedBits] = &[
+    ReservedBits {
+        addr: IspuReg::IspuConfig as u8,
+        name: "ISPU_CONFIG",
+        mask: not_used(IspuConfig::NOT_USED0_BITS, IspuConfig::NOT_USED0_OFFSET)
+            | not_used(IspuConfig::NOT_USED1_BITS, IspuConfig::NOT_USED1_OFFSET),
+        value: 0,
+    },
+    ReservedBits {
+        addr: IspuReg::IspuMemSel as u8,
+        name: "ISPU_MEM_SEL",
+        mask: not_used(IspuMemSel::NOT_USED0_BITS, IspuMemSel::NOT_USED0_OFFSET)
+            | not_used(IspuMemSel::NOT_USED1_BITS, IspuMemSel::NOT_USED1_OFFSET),
+        value: 0,
+    },
+    ReservedBits {
+        addr: IspuReg::IspuInt1Ctrl3 as u8,
+        name: "ISPU_INT1_CTRL3",
+        mask: not_used(
+            IspuInt1Ctrl3::NOT_USED0_BITS,
+            IspuInt1Ctrl3::NOT_USED0_OFFSET,
+        ),
+        value: 0,
+    },
+    ReservedBits {
+        addr: IspuReg::IspuInt2Ctrl3 as u8,
+        name: "ISPU_INT2_CTRL3",
+        mask: not_used(
+            IspuInt2Ctrl3::NOT_USED0_BITS,
+            IspuInt2Ctrl3::NOT_USED0_OFFSET,
+        ),
+        value: 0,
+    },
+];
+
 /// ISPU boot latched mode
 ///
 /// Controls ISPU boot latched mode.
@@ -1117,10 +1157,30 @@ pub enum IspuBootStatus {
 ///
 /// Selects ISPU memory type for access.
 #[repr(u8)]
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Default, TryFrom)]
+#[try_from(repr)]
 pub enum IspuMemoryType {
     /// Data RAM memory selected
+    #[default]
     DataRamMemory = 0x0,
     /// Program RAM memory selected
     ProgramRamMemory = 0x1,
 }
+
+register_bank! {
+    MemBank::IspuMemBank;
+    IspuConfig, IspuStatus, IspuMemSel, IspuMemAddr, IspuMemData, IspuIf2sFlag, IspuS2ifFlag,
+    IspuS2ifFlagL, IspuS2ifFlagH, IspuDout00L, IspuDout00H, IspuDout01L, IspuDout01H,
+    IspuDout02L, IspuDout02H, IspuDout03L, IspuDout03H, IspuDout04L, IspuDout04H, IspuDout05L,
+    IspuDout05H, IspuDout06L, IspuDout06H, IspuDout07L, IspuDout07H, IspuDout08L, IspuDout08H,
+    IspuDout09L, IspuDout09H, IspuDout10L, IspuDout10H, IspuDout11L, IspuDout11H, IspuDout12L,
+    IspuDout12H, IspuDout13L, IspuDout13H, IspuDout14L, IspuDout14H, IspuDout15L, IspuDout15H,
+    IspuDout16L, IspuDout16H, IspuDout17L, IspuDout17H, IspuDout18L, IspuDout18H, IspuDout19L,
+    IspuDout19H, IspuDout20L, IspuDout20H, IspuDout21L, IspuDout21H, IspuDout22L, IspuDout22H,
+    IspuDout23L, IspuDout23H, IspuDout24L, IspuDout24H, IspuDout25L, IspuDout25H, IspuDout26L,
+    IspuDout26H, IspuDout27L, IspuDout27H, IspuDout28L, IspuDout28H, IspuDout29L, IspuDout29H,
+    IspuDout30L, IspuDout30H, IspuDout31L, IspuDout31H, IspuInt1Ctrl, IspuInt1Ctrl0,
+    IspuInt1Ctrl1, IspuInt1Ctrl2, IspuInt1Ctrl3, IspuInt2Ctrl, IspuInt2Ctrl0, IspuInt2Ctrl1,
+    IspuInt2Ctrl2, IspuInt2Ctrl3, IspuIntStatus, IspuIntStatus0, IspuIntStatus1, IspuIntStatus2,
+    IspuIntStatus3, IspuAlgo,
+}
@@ -1,8 +1,11 @@
 use super::super::{
     BusOperation, DelayNs, Error, Lsm6dso16is, RegisterOperation, SensorOperation, bisync,
-    register::{BankState, MainBank},
+    register::{BankState, MainBank, MemBank, RegisterBank, register_bank},
 };
 
+#[cfg(feature = "debug_asserts")]
+use super::{ReservedBits, not_used};
+
 use bitfield_struct::bitfield;
 use derive_more::TryFrom;
 use st_mem_bank_macro::{named_register, register};
@@ -651,12 +654,128 @@ pub struct IspuDummyCfg4H {
     pub ispu_dummy_cfg_4: u8,
 }
 
+/// Reserved bits of the writable registers, checked by the `debug_asserts` feature.
+#[cfg(feature = "debug_asserts")]
+pub(crate) const RESERVED_BITS: &[ReservedBits] = &[
+    ReservedBits {
+        addr: Reg::FuncCfgAccess as u8,
+        name: "FUNC_CFG_ACCESS",
+        mask: not_used(
+            FuncCfgAccess::NOT_USED0_BITS,
+            FuncCfgAccess::NOT_USED0_OFFSET,
+        ) | not_used(
+            FuncCfgAccess::NOT_USED1_BITS,
+            FuncCfgAccess::NOT_USED1_OFFSET,
+        ),
+        value: 0,
+    },
+    ReservedBits {
+        addr: Reg::PinCtrl as u8,
+        name: "PIN_CTRL",
+        mask: not_used(PinCtrl::NOT_USED0_BITS, PinCtrl::NOT_USED0_OFFSET)
+            | not_used(PinCtrl::NOT_USED1_BITS, PinCtrl::NOT_USED1_OFFSET),
+        value: not_used(PinCtrl::NOT_USED0_BITS, PinCtrl::NOT_USED0_OFFSET),
+    },
+    ReservedBits {
+        addr: Reg::DrdyPulsedReg as u8,
+        name: "DRDY_PULSED_REG",
+        mask: not_used(
+            DrdyPulsedReg::NOT_USED0_BITS,
+            DrdyPulsedReg::NOT_USED0_OFFSET,
+        ),
+        value: 0,
+    },
+    ReservedBits {
+        addr: Reg::Int1Ctrl as u8,
+        name: "INT1_CTRL",
+        mask: not_used(Int1Ctrl::NOT_USED0_BITS, Int1Ctrl::NOT_USED0_OFFSET),
+        value: 0,
+    },
+    ReservedBits {
+        addr: Reg::Int2Ctrl as u8,
+        name: "INT2_CTRL",
+        mask: not_used(Int2Ctrl::NOT_USED0_BITS, Int2Ctrl::NOT_USED0_OFFSET),
+        value: 0,
+    },
+    ReservedBits {
+        addr: Reg::Ctrl1Xl as u8,
+        name: "CTRL1_XL",
+        mask: not_used(Ctrl1Xl::NOT_USED0_BITS, Ctrl1Xl::NOT_USED0_OFFSET),
+        value: 0,
+    },
+    ReservedBits {
+        addr: Reg::Ctrl2G as u8,
+        name: "CTRL2_G",
+        mask: not_used(Ctrl2G::NOT_USED0_BITS, Ctrl2G::NOT_USED0_OFFSET),
+        value: 0,
+    },
+    ReservedBits {
+        addr: Reg::Ctrl3C as u8,
+        name: "CTRL3_C",
+        mask: not_used(Ctrl3C::NOT_USED0_BITS, Ctrl3C::NOT_USED0_OFFSET),
+        value: 0,
+    },
+    ReservedBits {
+        addr: Reg::Ctrl4C as u8,
+        name: "CTRL4_C",
+        mask: not_used(Ctrl4C::NOT_USED0_BITS, Ctrl4C::NOT_USED0_OFFSET)
+            | not_used(Ctrl4C::NOT_USED1_BITS, Ctrl4C::NOT_USED1_OFFSET)
+            | not_used(Ctrl4C::NOT_USED2_BITS, Ctrl4C::NOT_USED2_OFFSET),
+        value: 0,
+    },
+    ReservedBits {
+        addr: Reg::Ctrl5C as u8,
+        name: "CTRL5_C",
+        mask: not_used(Ctrl5C::NOT_USED0_BITS, Ctrl5C::NOT_USED0_OFFSET),
+        value: 0,
+    },
+    ReservedBits {
+        addr: Reg::Ctrl6C as u8,
+        name: "CTRL6_C",
+        mask: not_used(Ctrl6C::NOT_USED0_BITS, Ctrl6C::NOT_USED0_OFFSET)
+            | not_used(Ctrl6C::NOT_USED1_BITS, Ctrl6C::NOT_USED1_OFFSET),
+        value: 0,
+    },
+    ReservedBits {
+        addr: Reg::Ctrl7G as u8,
+        name: "CTRL7_G",
+        mask: not_used(Ctrl7G::NOT_USED0_BITS, Ctrl7G::NOT_USED0_OFFSET),
+        value: 0,
+    },
+    ReservedBits {
+        addr: Reg::Ctrl9C as u8,
+        name: "CTRL9_C",
+        mask: not_used(Ctrl9C::NOT_USED0_BITS, Ctrl9C::NOT_USED0_OFFSET),
+        value: 0,
+    },
+    ReservedBits {
+        addr: Reg::Ctrl10C as u8,
+        name: "CTRL10_C",
+        mask: not_used(Ctrl10C::NOT_USED0_BITS, Ctrl10C::NOT_USED0_OFFSET)
+            | not_used(Ctrl10C::NOT_USED1_BITS, Ctrl10C::NOT_USED1_OFFSET)
+            | not_used(Ctrl10C::NOT_USED2_BITS, Ctrl10C::NOT_USED2_OFFSET),
+        value: 0,
+    },
+    ReservedBits {
+        addr: Reg::Md1Cfg as u8,
+        name: "MD1_CFG",
+        mask: not_used(Md1Cfg::NOT_USED0_BITS, Md1Cfg::NOT_USED0_OFFSET),
+        value: 0,
+    },
+    ReservedBits {
+        addr: Reg::Md2Cfg as u8,
+        name: "MD2_CFG",
+        mask: not_used(Md2Cfg::NOT_USED0_BITS, Md2Cfg::NOT_USED0_OFFSET),
+        value: 0,
+    },
+];
+
 /// It routes interrupt signals on INT 1 pin.
 ///
 /// The output of the INT1 pin is the OR combination of the signals selected here and in register MD1_CFG (5Eh).
 /// The signals include accelerometer data-ready, gyroscope data-ready, boot status, sensor hub communication
 /// concluded event, and ISPU event routing.
-#[derive(Default)]
+#[derive(Clone, Copy, PartialEq, Default)]
 pub struct PinInt1Route {
     /// Enables accelerometer data-ready interrupt on the INT1 pin.
     pub drdy_xl: u8,
@@ -675,7 +794,7 @@ pub struct PinInt1Route {
 /// The output of the INT2 pin is the OR combination of the signals selected here and in register MD2_CFG (5Fh).
 /// Signals include ISPU sleep state, temperature sensor data-ready, gyroscope data-ready, accelerometer data-ready,
 /// timestamp overflow alert, and ISPU event routing.
-#[derive(Default)]
+#[derive(Clone, Copy, PartialEq, Default)]
 pub struct PinInt2Route {
     /// Enables accelerometer data-ready interrupt on the INT2 pin.
     pub drdy_xl: u8,
@@ -727,6 +846,7 @@ pub enum HighPerformanceMode {
 /// Selects the full-scale range for the accelerometer.
 #[repr(u8)]
 #[derive(Clone, Copy, PartialEq, Default, TryFrom)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[try_from(repr)]
 pub enum XlFullScale {
     /// ±2 g full scale (default)
@@ -745,6 +865,7 @@ pub enum XlFullScale {
 /// Includes both high-performance and low-power mode ODRs.
 #[repr(u8)]
 #[derive(Clone, Copy, PartialEq, Default, TryFrom)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[try_from(repr)]
 pub enum XlDataRate {
     /// Power-down mode
@@ -794,11 +915,69 @@ pub enum XlDataRate {
     _1_6hzLp = 0x1b,
 }
 
+impl XlDataRate {
+    /// Short human-readable label of the rate and power mode, e.g.
+    /// `"52hz hp"`, for logs and UIs.
+    pub fn label(&self) -> &'static str {
+        match self {
+            XlDataRate::Off => "off",
+            XlDataRate::_12_5hzHp => "12.5hz hp",
+            XlDataRate::_26hzHp => "26hz hp",
+            XlDataRate::_52hzHp => "52hz hp",
+            XlDataRate::_104hzHp => "104hz hp",
+            XlDataRate::_208hzHp => "208hz hp",
+            XlDataRate::_416hzHp => "416hz hp",
+            XlDataRate::_833hzHp => "833hz hp",
+            XlDataRate::_1667hzHp => "1667hz hp",
+            XlDataRate::_3333hzHp => "3333hz hp",
+            XlDataRate::_6667hzHp => "6667hz hp",
+            XlDataRate::_12_5hzLp => "12.5hz lp",
+            XlDataRate::_26hzLp => "26hz lp",
+            XlDataRate::_52hzLp => "52hz lp",
+            XlDataRate::_104hzLp => "104hz lp",
+            XlDataRate::_208hzLp => "208hz lp",
+            XlDataRate::_416hzLp => "416hz lp",
+            XlDataRate::_833hzLp => "833hz lp",
+            XlDataRate::_1667hzLp => "1667hz lp",
+            XlDataRate::_3333hzLp => "3333hz lp",
+            XlDataRate::_6667hzLp => "6667hz lp",
+            XlDataRate::_1_6hzLp => "1.6hz lp",
+        }
+    }
+}
+
+/// All the accelerometer output data rates, e.g. to build a selection menu.
+pub const XL_DATA_RATES: &[XlDataRate] = &[
+    XlDataRate::Off,
+    XlDataRate::_12_5hzHp,
+    XlDataRate::_26hzHp,
+    XlDataRate::_52hzHp,
+    XlDataRate::_104hzHp,
+    XlDataRate::_208hzHp,
+    XlDataRate::_416hzHp,
+    XlDataRate::_833hzHp,
+    XlDataRate::_1667hzHp,
+    XlDataRate::_3333hzHp,
+    XlDataRate::_6667hzHp,
+    XlDataRate::_12_5hzLp,
+    XlDataRate::_26hzLp,
+    XlDataRate::_52hzLp,
+    XlDataRate::_104hzLp,
+    XlDataRate::_208hzLp,
+    XlDataRate::_416hzLp,
+    XlDataRate::_833hzLp,
+    XlDataRate::_1667hzLp,
+    XlDataRate::_3333hzLp,
+    XlDataRate::_6667hzLp,
+    XlDataRate::_1_6hzLp,
+];
+
 /// Gyroscope full-scale selection
 ///
 /// Selects the full-scale range for the gyroscope.
 #[repr(u8)]
 #[derive(Clone, Copy, PartialEq, Default, TryFrom)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[try_from(repr)]
 pub enum GyFullScale {
     /// ±250 dps full scale
@@ -814,11 +993,22 @@ pub enum GyFullScale {
     _125dps = 0x10,
 }
 
+impl GyFullScale {
+    /// Returns true for the ±125 dps full scale.
+    ///
+    /// ±125 dps is selected by the separate FS_125 bit of CTRL2_G (encoded
+    /// here as 0x10), which overrides FS_G.
+    pub fn is_125dps(&self) -> bool {
+        *self == GyFullScale::_125dps
+    }
+}
+
 /// Gyroscope output data rate (ODR)
 ///
 /// Includes both high-performance and low-power mode ODRs.
 #[repr(u8)]
 #[derive(Clone, Copy, PartialEq, Default, TryFrom)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[try_from(repr)]
 pub enum GyDataRate {
     /// Power-down mode
@@ -865,6 +1055,61 @@ pub enum GyDataRate {
     /// 6667 Hz ODR in low-power mode
     _6667hzLp = 0x1a,
 }
+
+impl GyDataRate {
+    /// Short human-readable label of the rate and power mode, e.g.
+    /// `"208hz lp"`, for logs and UIs.
+    pub fn label(&self) -> &'static str {
+        match self {
+            GyDataRate::Off => "off",
+            GyDataRate::_12_5hzHp => "12.5hz hp",
+            GyDataRate::_26hzHp => "26hz hp",
+            GyDataRate::_52hzHp => "52hz hp",
+            GyDataRate::_104hzHp => "104hz hp",
+            GyDataRate::_208hzHp => "208hz hp",
+            GyDataRate::_416hzHp => "416hz hp",
+            GyDataRate::_833hzHp => "833hz hp",
+            GyDataRate::_1667hzHp => "1667hz hp",
+            GyDataRate::_3333hzHp => "3333hz hp",
+            GyDataRate::_6667hzHp => "6667hz hp",
+            GyDataRate::_12_5hzLp => "12.5hz lp",
+            GyDataRate::_26hzLp => "26hz lp",
+            GyDataRate::_52hzLp => "52hz lp",
+            GyDataRate::_104hzLp => "104hz lp",
+            GyDataRate::_208hzLp => "208hz lp",
+            GyDataRate::_416hzLp => "416hz lp",
+            GyDataRate::_833hzLp => "833hz lp",
+            GyDataRate::_1667hzLp => "1667hz lp",
+            GyDataRate::_3333hzLp => "3333hz lp",
+            GyDataRate::_6667hzLp => "6667hz lp",
+        }
+    }
+}
+
+/// All the gyroscope output data rates, e.g. to build a selection menu.
+pub const GY_DATA_RATES: &[GyDataRate] = &[
+    GyDataRate::Off,
+    GyDataRate::_12_5hzHp,
+    GyDataRate::_26hzHp,
+    GyDataRate::_52hzHp,
+    GyDataRate::_104hzHp,
+    GyDataRate::_208hzHp,
+    GyDataRate::_416hzHp,
+    GyDataRate::_833hzHp,
+    GyDataRate::_1667hzHp,
+    GyDataRate::_3333hzHp,
+    GyDataRate::_6667hzHp,
+    GyDataRate::_12_5hzLp,
+    GyDataRate::_26hzLp,
+    GyDataRate::_52hzLp,
+    GyDataRate::_104hzLp,
+    GyDataRate::_208hzLp,
+    GyDataRate::_416hzLp,
+    GyDataRate::_833hzLp,
+    GyDataRate::_1667hzLp,
+    GyDataRate::_3333hzLp,
+    GyDataRate::_6667hzLp,
+];
 /// Sleep mode for gyroscope
 ///
 /// Enables or disables the gyroscope independently of the accelerometer.
@@ -1054,3 +1299,13 @@ pub enum IspuBdu {
     /// and BDU on 4 bytes (8 outpus) for ISPU_DOUT_16_L to ISPU_DOUT_31_H
     On4b4b = 0x3,
 }
+
+// FUNC_CFG_ACCESS is reachable from every bank; its home is the main bank.
+register_bank! {
+    MemBank::MainMemBank;
+    FuncCfgAccess, PinCtrl, DrdyPulsedReg, Int1Ctrl, Int2Ctrl, WhoAmI, Ctrl1Xl, Ctrl2G, Ctrl3C,
+    Ctrl4C, Ctrl5C, Ctrl6C, Ctrl7G, Ctrl9C, Ctrl10C, IspuIntStatusMainPage, StatusReg, OutTemp,
+    OutXYZG, OutXYZA, StatusMasterMainpage, Timestamp, Md1Cfg, Md2Cfg, InternalFreqFine,
+    IspuDummyCfg1L, IspuDummyCfg1H, IspuDummyCfg2L, IspuDummyCfg2H, IspuDummyCfg3L,
+    IspuDummyCfg3H, IspuDummyCfg4L, IspuDummyCfg4H,
+}
@@ -27,6 +27,88 @@ pub enum MemBank {
     IspuMemBank = 0x3,
 }
 
+/// Memory bank a register belongs to.
+///
+/// Implemented for every register type; lets generic code select the right
+/// bank before accessing a register.
+pub trait RegisterBank {
+    /// Bank that must be selected to access the register.
+    const BANK: MemBank;
+
+    /// Returns the bank the register belongs to.
+    fn bank() -> MemBank {
+        Self::BANK
+    }
+}
+
+/// Implement `RegisterBank` with the same bank for a list of registers.
+macro_rules! register_bank {
+    ($bank:expr; $($reg:ty),* $(,)?) => {
+        $(
+            impl RegisterBank for $reg {
+                const BANK: MemBank = $bank;
+            }
+        )*
+    };
+}
+pub(crate) use register_bank;
+
+/// Reserved (`not_used`) bits of a register and the value they must hold.
+#[cfg(feature = "debug_asserts")]
+pub(crate) struct ReservedBits {
+    pub addr: u8,
+    pub name: &'static str,
+    pub mask: u8,
+    pub value: u8,
+}
+
+/// Mask of a `not_used` field, from its bitfield width and offset.
+#[cfg(feature = "debug_asserts")]
+pub(crate) const fn not_used(bits: usize, offset: usize) -> u8 {
+    (((1u16 << bits) - 1) << offset) as u8
+}
+
+/// Assert that a register write does not change reserved bits.
+///
+/// `buf` is written starting at `reg` in `bank`; every byte is checked
+/// against the reserved bits of the register it lands on.
+#[cfg(feature = "debug_asserts")]
+pub(crate) fn assert_reserved_bits(bank: MemBank, reg: u8, buf: &[u8]) {
+    let bank = match bank {
+        MemBank::SensorHubMemBank => sensor_hub::RESERVED_BITS,
+        MemBank::IspuMemBank => {
+            // The memory data port does not auto-increment
+            if reg == ispu::IspuReg::IspuMemData as u8 {
+                return;
+            }
+            ispu::RESERVED_BITS
+        }
+        MemBank::MainMemBank => main::RESERVED_BITS,
+    };
+
+    for (i, byte) in buf.iter().enumerate() {
+        let addr = reg.wrapping_add(i as u8);
+        // FUNC_CFG_ACCESS is reachable from every bank
+        let table = if addr == main::Reg::FuncCfgAccess as u8 {
+            main::RESERVED_BITS
+        } else {
+            bank
+        };
+
+        if let Some(reserved) = table.iter().find(|r| r.addr == addr) {
+            debug_assert!(
+                byte & reserved.mask == reserved.value,
+                "write of {:#04x} to {} ({:#04x}) changes reserved bits {:#04x} (expected {:#04x})",
+                byte,
+                reserved.name,
+                addr,
+                reserved.mask,
+                reserved.value
+            );
+        }
+    }
+}
+
 #[bisync]
 impl<B, T> Lsm6dso16is<B, T, IspuBank>
 where
@@ -1,12 +1,19 @@
 use super::super::{
     BusOperation, DelayNs, Error, Lsm6dso16is, RegisterOperation, SensorOperation, bisync,
-    register::SensorHubBank,
+    register::{MemBank, RegisterBank, SensorHubBank, register_bank},
 };
 
+#[cfg(feature = "debug_asserts")]
+use super::{ReservedBits, not_used};
+
 use bitfield_struct::bitfield;
 use derive_more::TryFrom;
 use st_mem_bank_macro::register;
 
+/// Number of sensor hub output registers (SENSOR_HUB_1 to SENSOR_HUB_18)
+/// holding the data read from the external sensors.
+pub const SENSOR_HUB_REGISTER_COUNT: usize = 18;
+
 #[repr(u8)]
 #[derive(Clone, Copy, PartialEq)]
 pub enum SensHubReg {
@@ -518,6 +525,35 @@ pub struct StatusMaster {
     pub wr_once_done: u8,
 }
 
+/// Reserved bits of the writable registers, checked by the `debug_asserts` feature.
+#[cfg(feature = "debug_asserts")]
+pub(crate) const RESERVED_BITS: &[ReservedBits] = &[
+    ReservedBits {
+        addr: SensHubReg::Slv0Config as u8,
+        name: "SLV0_CONFIG",
+        mask: not_used(Slv0Config::NOT_USED0_BITS, Slv0Config::NOT_USED0_OFFSET),
+        value: 0,
+    },
+    ReservedBits {
+        addr: SensHubReg::Slv1Config as u8,
+        name: "SLV1_CONFIG",
+        mask: not_used(Slv1Config::NOT_USED0_BITS, Slv1Config::NOT_USED0_OFFSET),
+        value: 0,
+    },
+    ReservedBits {
+        addr: SensHubReg::Slv2Config as u8,
+        name: "SLV2_CONFIG",
+        mask: not_used(Slv2Config::NOT_USED0_BITS, Slv2Config::NOT_USED0_OFFSET),
+        value: 0,
+    },
+    ReservedBits {
+        addr: SensHubReg::Slv3Config as u8,
+        name: "SLV3_CONFIG",
+        mask: not_used(Slv3Config::NOT_USED0_BITS, Slv3Config::NOT_USED0_OFFSET),
+        value: 0,
+    },
+];
+
 /// Sensor hub connected slaves configuration
 ///
 /// Selects the number of external sensors connected to the sensor hub.
@@ -536,6 +572,16 @@ pub enum ShSlaveConnected {
     _0123 = 0x3,
 }
 
+impl ShSlaveConnected {
+    /// Number of connected slaves.
+    ///
+    /// AUX_SENS_ON holds the number of slaves minus one:
+    /// `_0` = 1, `_01` = 2, `_012` = 3, `_0123` = 4.
+    pub fn count(&self) -> u8 {
+        (*self as u8) + 1
+    }
+}
+
 /// Sensor hub synchronization mode
 ///
 /// Selects the trigger signal for the sensor hub.
@@ -582,6 +628,19 @@ pub enum ShDataRate {
     _12_5hz = 0x3,
 }
 
+impl ShDataRate {
+    /// Width of the SHUB_ODR field in SLV0_CONFIG.
+    pub const FIELD_MASK: u8 = 0x03;
+
+    /// Raw SHUB_ODR field value for this rate.
+    ///
+    /// The effective rate never exceeds the faster of the accelerometer and
+    /// gyroscope ODRs, since the sensor hub is triggered by them.
+    pub fn as_bits(&self) -> u8 {
+        *self as u8
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Default)]
 pub struct ShCfgWrite {
     pub slv0_add: u8,
@@ -595,3 +654,10 @@ pub struct ShCfgRead {
     pub slv_subadd: u8,
     pub slv_len: u8,
 }
+
+register_bank! {
+    MemBank::SensorHubMemBank;
+    SensorHub1, MasterConfig, Slv0Add, Slv0Subadd, Slv0Config, Slv1Add, Slv1Subadd, Slv1Config,
+    Slv2Add, Slv2Subadd, Slv2Config, Slv3Add, Slv3Subadd, Slv3Config, DatawriteSlv0,
+    StatusMaster,
+}
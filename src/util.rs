@@ -0,0 +1,20 @@
+//! Byte decoding helpers for sensor hub payloads.
+//!
+//! External sensors read through the sensor hub land in SENSOR_HUB_x as raw
+//! little-endian bytes; these helpers decode the common 16-bit and 24-bit
+//! layouts (e.g. LIS2MDL magnetic field, LPS22DF pressure).
+
+/// Decode a little-endian signed 16-bit value.
+pub fn le_i16(bytes: [u8; 2]) -> i16 {
+    i16::from_le_bytes(bytes)
+}
+
+/// Decode a little-endian signed 24-bit value, sign-extended to `i32`.
+pub fn le_i24(bytes: [u8; 3]) -> i32 {
+    i32::from_le_bytes([0, bytes[0], bytes[1], bytes[2]]) >> 8
+}
+
+/// Decode a little-endian unsigned 24-bit value.
+pub fn le_u24(bytes: [u8; 3]) -> u32 {
+    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0])
+}
@@ -0,0 +1,109 @@
+#![cfg(feature = "async")]
+
+mod common;
+
+use common::{ISPU, MockBus, MockDelay, block_on};
+use lsm6dso16is_rs::asynchronous::Lsm6dso16is;
+use lsm6dso16is_rs::asynchronous::prelude::*;
+use st_mems_bus::asynchronous::BusOperation;
+
+const FUNC_CFG_ACCESS: u8 = Reg::FuncCfgAccess as u8;
+
+fn sensor() -> Lsm6dso16is<MockBus, MockDelay, MainBank> {
+    Lsm6dso16is::from_bus(MockBus::new(), MockDelay::default())
+}
+
+#[test]
+fn current_bank_reports_the_selected_bank() {
+    let mut sensor = sensor();
+    assert_eq!(
+        block_on(sensor.current_bank()).unwrap(),
+        MemBank::MainMemBank
+    );
+
+    let shub = FuncCfgAccess::new().with_shub_reg_access(1).into_bits();
+    sensor.bus.set_main(FUNC_CFG_ACCESS, shub);
+    assert_eq!(
+        block_on(sensor.current_bank()).unwrap(),
+        MemBank::SensorHubMemBank
+    );
+
+    let ispu = FuncCfgAccess::new().with_ispu_reg_access(1).into_bits();
+    sensor.bus.set_main(FUNC_CFG_ACCESS, ispu);
+    assert_eq!(
+        block_on(sensor.current_bank()).unwrap(),
+        MemBank::IspuMemBank
+    );
+}
+
+#[test]
+fn register_bank_reports_each_register_home() {
+    assert_eq!(Ctrl3C::bank(), MemBank::MainMemBank);
+    assert_eq!(IspuConfig::bank(), MemBank::IspuMemBank);
+    assert_eq!(MasterConfig::bank(), MemBank::SensorHubMemBank);
+}
+
+#[test]
+fn register_bank_selects_the_bank_to_access_a_register() {
+    use st_mems_bus::asynchronous::MemBankFunctions;
+
+    let mut sensor = sensor();
+    sensor.bus.regs[ISPU][IspuReg::IspuConfig as usize] = 0x5A;
+
+    block_on(sensor.mem_bank_set(IspuConfig::bank())).unwrap();
+    let mut val = [0];
+    block_on(
+        sensor
+            .bus
+            .write_byte_read_bytes(&[IspuReg::IspuConfig as u8], &mut val),
+    )
+    .unwrap();
+    assert_eq!(val, [0x5A]);
+    assert_eq!(block_on(sensor.current_bank()).unwrap(), IspuConfig::bank());
+}
+
+#[test]
+fn func_cfg_access_get_decodes_the_bank_and_reset_bits() {
+    let mut sensor = sensor();
+    let reg = block_on(sensor.func_cfg_access_get()).unwrap();
+    assert_eq!(
+        (
+            reg.shub_reg_access(),
+            reg.ispu_reg_access(),
+            reg.sw_reset_ispu()
+        ),
+        (0, 0, 0)
+    );
+
+    sensor.bus.set_main(
+        FUNC_CFG_ACCESS,
+        FuncCfgAccess::new()
+            .with_ispu_reg_access(1)
+            .with_sw_reset_ispu(1)
+            .into_bits(),
+    );
+    let reg = block_on(sensor.func_cfg_access_get()).unwrap();
+    assert_eq!(
+        (
+            reg.shub_reg_access(),
+            reg.ispu_reg_access(),
+            reg.sw_reset_ispu()
+        ),
+        (0, 1, 1)
+    );
+
+    sensor.bus.set_main(
+        FUNC_CFG_ACCESS,
+        FuncCfgAccess::new().with_shub_reg_access(1).into_bits(),
+    );
+    let reg = block_on(sensor.func_cfg_access_get()).unwrap();
+    assert_eq!(
+        (
+            reg.shub_reg_access(),
+            reg.ispu_reg_access(),
+            reg.sw_reset_ispu()
+        ),
+        (1, 0, 0)
+    );
+    assert!(sensor.bus.writes.is_empty());
+}
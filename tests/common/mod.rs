@@ -0,0 +1,308 @@
+//! In-memory register map standing in for the device in integration tests.
+#![allow(dead_code)]
+
+use core::future::Future;
+use core::pin::pin;
+use core::task::{Context, Poll, Waker};
+use std::collections::VecDeque;
+
+use lsm6dso16is_rs::asynchronous::prelude::{FuncCfgAccess, IspuMemSel, IspuReg};
+use st_mems_bus::asynchronous::BusOperation;
+
+/// FUNC_CFG_ACCESS, reachable from every bank.
+const FUNC_CFG_ACCESS: u8 = 0x01;
+
+/// Main page, sensor hub and ISPU banks, as indexes into `MockBus::regs`.
+pub const MAIN: usize = 0;
+pub const SENSOR_HUB: usize = 1;
+pub const ISPU: usize = 2;
+
+/// Hook run before every register read, with the register map and the
+/// first address read; lets a test model registers the device updates.
+pub type ReadHook = Box<dyn FnMut(&mut [[u8; 256]; 3], u8)>;
+
+/// Bus backed by three 256-byte register banks (main, sensor hub, ISPU).
+///
+/// The bank is selected by the SHUB_REG_ACCESS/ISPU_REG_ACCESS bits of
+/// FUNC_CFG_ACCESS, like on the device; addresses always auto-increment.
+/// Every write is recorded, and reads of a register can be scripted to
+/// return a sequence of values before falling back to the register map.
+pub struct MockBus {
+    pub regs: [[u8; 256]; 3],
+    /// Writes in order, as (bank, first address, payload).
+    pub writes: Vec<(usize, u8, Vec<u8>)>,
+    /// Number of bus transactions performed.
+    pub transactions: usize,
+    /// Transaction (0-based) that fails with a bus error, if any.
+    pub fail_at: Option<usize>,
+    /// (bank, address) whose next write fails with a bus error, once.
+    pub fail_write: Option<(usize, u8)>,
+    pub on_read: Option<ReadHook>,
+    /// ISPU data RAM and program RAM, reached through ISPU_MEM_SEL,
+    /// ISPU_MEM_ADDR and ISPU_MEM_DATA.
+    pub ispu_mem: [Vec<u8>; 2],
+    /// ISPU memory cell that ignores writes, to model a corrupted load.
+    pub ispu_mem_stuck: Option<u16>,
+    ispu_mem_ptr: usize,
+    ispu_mem_primed: bool,
+    scripts: Vec<(usize, u8, VecDeque<u8>)>,
+}
+
+impl MockBus {
+    pub fn new() -> Self {
+        MockBus {
+            regs: [[0; 256]; 3],
+            writes: Vec::new(),
+            transactions: 0,
+            fail_at: None,
+            fail_write: None,
+            on_read: None,
+            ispu_mem: [vec![0; 0x10000], vec![0; 0x10000]],
+            ispu_mem_stuck: None,
+            ispu_mem_ptr: 0,
+            ispu_mem_primed: false,
+            scripts: Vec::new(),
+        }
+    }
+
+    /// Main page register.
+    pub fn main(&self, addr: u8) -> u8 {
+        self.regs[MAIN][addr as usize]
+    }
+
+    pub fn set_main(&mut self, addr: u8, val: u8) {
+        self.regs[MAIN][addr as usize] = val;
+    }
+
+    /// Sensor hub bank register.
+    pub fn sensor_hub(&self, addr: u8) -> u8 {
+        self.regs[SENSOR_HUB][addr as usize]
+    }
+
+    /// Make the next reads of `addr` in `bank` return `values`, one per
+    /// read; the last value stays in the register map afterwards.
+    pub fn script(&mut self, bank: usize, addr: u8, values: &[u8]) {
+        self.scripts
+            .push((bank, addr, values.iter().copied().collect()));
+    }
+
+    /// Payloads written to `addr` in `bank`, oldest first.
+    pub fn writes_to(&self, bank: usize, addr: u8) -> Vec<&[u8]> {
+        self.writes
+            .iter()
+            .filter(|(b, a, _)| *b == bank && *a == addr)
+            .map(|(_, _, data)| data.as_slice())
+            .collect()
+    }
+
+    fn bank(&self, addr: u8) -> usize {
+        let func_cfg_access = FuncCfgAccess::from_bits(self.regs[MAIN][FUNC_CFG_ACCESS as usize]);
+        if addr == FUNC_CFG_ACCESS {
+            MAIN
+        } else if func_cfg_access.shub_reg_access() == 1 {
+            SENSOR_HUB
+        } else if func_cfg_access.ispu_reg_access() == 1 {
+            ISPU
+        } else {
+            MAIN
+        }
+    }
+
+    fn ispu_mem_sel(&self) -> usize {
+        IspuMemSel::from_bits(self.regs[ISPU][IspuReg::IspuMemSel as usize]).mem_sel() as usize
+    }
+
+    fn is_ispu_mem_data(&self, addr: u8) -> bool {
+        self.bank(addr) == ISPU && addr == IspuReg::IspuMemData as u8
+    }
+
+    fn transaction(&mut self) -> Result<(), ()> {
+        let index = self.transactions;
+        self.transactions += 1;
+        match self.fail_at {
+            Some(fail_at) if fail_at == index => Err(()),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl BusOperation for MockBus {
+    type Error = ();
+
+    async fn read_bytes(&mut self, _rbuf: &mut [u8]) -> Result<(), Self::Error> {
+        Err(())
+    }
+
+    async fn write_bytes(&mut self, wbuf: &[u8]) -> Result<(), Self::Error> {
+        self.transaction()?;
+        let reg = wbuf[0];
+        if self.fail_write == Some((self.bank(reg), reg)) {
+            self.fail_write = None;
+            return Err(());
+        }
+        self.writes.push((self.bank(reg), reg, wbuf[1..].to_vec()));
+        if self.is_ispu_mem_data(reg) {
+            // ISPU_MEM_DATA does not auto-increment: the memory address does.
+            let sel = self.ispu_mem_sel();
+            for byte in &wbuf[1..] {
+                if self.ispu_mem_stuck != Some(self.ispu_mem_ptr as u16) {
+                    self.ispu_mem[sel][self.ispu_mem_ptr] = *byte;
+                }
+                self.ispu_mem_ptr = (self.ispu_mem_ptr + 1) & 0xFFFF;
+            }
+            return Ok(());
+        }
+        for (i, byte) in wbuf[1..].iter().enumerate() {
+            let addr = reg.wrapping_add(i as u8);
+            let bank = self.bank(addr);
+            self.regs[bank][addr as usize] = *byte;
+        }
+        let mem_addr = [IspuReg::IspuMemAddr0 as u8, IspuReg::IspuMemAddr1 as u8];
+        if self.bank(reg) == ISPU
+            && (0..wbuf.len() - 1).any(|i| mem_addr.contains(&reg.wrapping_add(i as u8)))
+        {
+            self.ispu_mem_ptr = u16::from_le_bytes([
+                self.regs[ISPU][IspuReg::IspuMemAddr0 as usize],
+                self.regs[ISPU][IspuReg::IspuMemAddr1 as usize],
+            ]) as usize;
+            self.ispu_mem_primed = false;
+        }
+
+        Ok(())
+    }
+
+    async fn write_byte_read_bytes(
+        &mut self,
+        wbuf: &[u8; 1],
+        rbuf: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.transaction()?;
+        let reg = wbuf[0];
+        if let Some(hook) = self.on_read.as_mut() {
+            hook(&mut self.regs, reg);
+        }
+        if self.is_ispu_mem_data(reg) {
+            // The first read after setting the address returns a dummy byte.
+            let sel = self.ispu_mem_sel();
+            for byte in rbuf.iter_mut() {
+                if self.ispu_mem_primed {
+                    *byte = self.ispu_mem[sel][self.ispu_mem_ptr];
+                    self.ispu_mem_ptr = (self.ispu_mem_ptr + 1) & 0xFFFF;
+                } else {
+                    *byte = 0;
+                    self.ispu_mem_primed = true;
+                }
+            }
+            return Ok(());
+        }
+        for (i, byte) in rbuf.iter_mut().enumerate() {
+            let addr = reg.wrapping_add(i as u8);
+            let bank = self.bank(addr);
+            if let Some((_, _, values)) = self
+                .scripts
+                .iter_mut()
+                .find(|(b, a, values)| *b == bank && *a == addr && !values.is_empty())
+            {
+                self.regs[bank][addr as usize] = values.pop_front().unwrap();
+            }
+            *byte = self.regs[bank][addr as usize];
+        }
+
+        Ok(())
+    }
+}
+
+/// Delay that returns immediately and counts how often it was called.
+#[derive(Default)]
+pub struct MockDelay {
+    pub calls: usize,
+    pub total_ns: u64,
+}
+
+impl embedded_hal_async::delay::DelayNs for MockDelay {
+    async fn delay_ns(&mut self, ns: u32) {
+        self.calls += 1;
+        self.total_ns += u64::from(ns);
+    }
+}
+
+/// Run a future that never waits on anything external to completion.
+pub fn block_on<F: Future>(fut: F) -> F::Output {
+    let mut fut = pin!(fut);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(val) = fut.as_mut().poll(&mut cx) {
+            return val;
+        }
+    }
+}
+
+/// I2C controller that acknowledges everything and reads zeros.
+pub struct NullI2c;
+
+impl embedded_hal_async::i2c::ErrorType for NullI2c {
+    type Error = core::convert::Infallible;
+}
+
+impl embedded_hal_async::i2c::I2c for NullI2c {
+    async fn transaction(
+        &mut self,
+        _address: u8,
+        operations: &mut [embedded_hal_async::i2c::Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        for op in operations {
+            if let embedded_hal_async::i2c::Operation::Read(buf) = op {
+                buf.fill(0);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// I2C controller like `NullI2c` that records the target address of every
+/// transaction.
+#[derive(Default)]
+pub struct RecordingI2c {
+    pub addresses: Vec<u8>,
+}
+
+impl embedded_hal_async::i2c::ErrorType for RecordingI2c {
+    type Error = core::convert::Infallible;
+}
+
+impl embedded_hal_async::i2c::I2c for RecordingI2c {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [embedded_hal_async::i2c::Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.addresses.push(address);
+        NullI2c.transaction(address, operations).await
+    }
+}
+
+/// SPI device that accepts everything and reads zeros.
+pub struct NullSpi;
+
+impl embedded_hal_async::spi::ErrorType for NullSpi {
+    type Error = core::convert::Infallible;
+}
+
+impl embedded_hal_async::spi::SpiDevice for NullSpi {
+    async fn transaction(
+        &mut self,
+        operations: &mut [embedded_hal_async::spi::Operation<'_, u8>],
+    ) -> Result<(), Self::Error> {
+        for op in operations {
+            match op {
+                embedded_hal_async::spi::Operation::Read(buf) => buf.fill(0),
+                embedded_hal_async::spi::Operation::Transfer(buf, _)
+                | embedded_hal_async::spi::Operation::TransferInPlace(buf) => buf.fill(0),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,9 @@
+// With both APIs enabled rustc prints fully qualified type paths, so the
+// expected diagnostics are only recorded for the async-only build.
+#![cfg(all(feature = "async", not(feature = "blocking")))]
+
+#[test]
+fn main_bank_methods_are_unavailable_on_other_banks() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,612 @@
+#![cfg(feature = "async")]
+
+mod common;
+
+use common::{MAIN, MockBus, MockDelay, block_on};
+use lsm6dso16is_rs::asynchronous::prelude::*;
+use lsm6dso16is_rs::asynchronous::{
+    AccelUnit, AngularUnit, DataReadySource, Error, Lsm6dso16is, TempScale, convert_gy_block,
+    convert_xl_block, from_fs2g_to_mg, from_fs8g_to_mg, from_fs250dps_to_mdps,
+    from_fs500dps_to_mdps,
+};
+#[cfg(feature = "integer_conversions")]
+use lsm6dso16is_rs::asynchronous::{from_lsb_to_mdps, from_lsb_to_mg};
+
+fn sensor() -> Lsm6dso16is<MockBus, MockDelay, MainBank> {
+    Lsm6dso16is::from_bus(MockBus::new(), MockDelay::default())
+}
+
+/// Store little-endian `[x, y, z]` samples starting at `addr`.
+fn set_axes(bus: &mut MockBus, addr: u8, axes: [i16; 3]) {
+    for (i, axis) in axes.iter().enumerate() {
+        let [l, h] = axis.to_le_bytes();
+        bus.set_main(addr + 2 * i as u8, l);
+        bus.set_main(addr + 2 * i as u8 + 1, h);
+    }
+}
+
+fn assert_close(actual: [f32; 3], expected: [f32; 3]) {
+    for (a, e) in actual.iter().zip(expected) {
+        assert!(
+            (a - e).abs() <= e.abs() * 1e-5,
+            "{actual:?} != {expected:?}"
+        );
+    }
+}
+
+#[test]
+fn acceleration_get_converts_to_each_unit() {
+    let mut sensor = sensor();
+    // Default full scale is ±2 g, 0.061 mg/LSB
+    set_axes(&mut sensor.bus, Reg::OutxLA as u8, [1000, -1000, 2000]);
+
+    let mg = [61.0, -61.0, 122.0];
+    assert_close(
+        block_on(sensor.acceleration_get(AccelUnit::Mg)).unwrap(),
+        mg,
+    );
+    assert_close(
+        block_on(sensor.acceleration_get(AccelUnit::G)).unwrap(),
+        mg.map(|v| v / 1000.0),
+    );
+    assert_close(
+        block_on(sensor.acceleration_get(AccelUnit::MetersPerSec2)).unwrap(),
+        mg.map(|v| v / 1000.0 * 9.80665),
+    );
+}
+
+#[test]
+fn angular_rate_get_converts_to_each_unit() {
+    let mut sensor = sensor();
+    block_on(sensor.gy_full_scale_set(GyFullScale::_250dps)).unwrap();
+    // ±250 dps, 8.75 mdps/LSB
+    set_axes(&mut sensor.bus, Reg::OutxLG as u8, [1000, -1000, 2000]);
+
+    let mdps = [8750.0, -8750.0, 17500.0];
+    assert_close(
+        block_on(sensor.angular_rate_get(AngularUnit::Mdps)).unwrap(),
+        mdps,
+    );
+    assert_close(
+        block_on(sensor.angular_rate_get(AngularUnit::Dps)).unwrap(),
+        mdps.map(|v| v / 1000.0),
+    );
+    assert_close(
+        block_on(sensor.angular_rate_get(AngularUnit::RadPerSec)).unwrap(),
+        mdps.map(|v| (v / 1000.0).to_radians()),
+    );
+}
+
+#[test]
+fn average_samples_averages_fresh_samples() {
+    let mut sensor = sensor();
+    let xlda = StatusReg::new().with_xlda(1).into_bits();
+    sensor.bus.set_main(Reg::StatusReg as u8, xlda);
+    sensor
+        .bus
+        .script(MAIN, Reg::OutxLA as u8, &[10, 20, 30, 40]);
+    sensor
+        .bus
+        .script(MAIN, Reg::OutzLA as u8, &[0xfe, 0xfe, 0xfe, 0xfe]);
+    sensor
+        .bus
+        .script(MAIN, Reg::OutzHA as u8, &[0xff, 0xff, 0xff, 0xff]);
+
+    let avg =
+        block_on(sensor.average_samples(4, DataReadySource::Xl, Lsm6dso16is::acceleration_raw_get))
+            .unwrap();
+
+    assert_eq!(avg, [25.0, 0.0, -2.0]);
+    // One STATUS_REG poll and one sample read per sample
+    assert_eq!(sensor.bus.transactions, 8);
+}
+
+#[test]
+fn average_samples_rejects_zero_samples() {
+    let mut sensor = sensor();
+
+    let result = block_on(sensor.average_samples(0, DataReadySource::Xl, async |s| {
+        s.acceleration_raw_get().await
+    }));
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+}
+
+#[test]
+fn full_samples_pair_raw_counts_with_converted_values() {
+    let mut sensor = sensor();
+    block_on(sensor.xl_full_scale_set(XlFullScale::_8g)).unwrap();
+    block_on(sensor.gy_full_scale_set(GyFullScale::_500dps)).unwrap();
+    set_axes(&mut sensor.bus, Reg::OutxLA as u8, [100, -200, 300]);
+    set_axes(&mut sensor.bus, Reg::OutxLG as u8, [-100, 200, -300]);
+
+    let xl = block_on(sensor.acceleration_full()).unwrap();
+    assert_eq!(xl.raw, [100, -200, 300]);
+    assert_eq!(xl.mg, xl.raw.map(from_fs8g_to_mg));
+
+    let gy = block_on(sensor.angular_rate_full()).unwrap();
+    assert_eq!(gy.raw, [-100, 200, -300]);
+    assert_eq!(gy.mdps, gy.raw.map(from_fs500dps_to_mdps));
+}
+
+#[test]
+fn read_sample_converts_with_the_configured_full_scales() {
+    let mut sensor = sensor();
+    block_on(sensor.xl_full_scale_set(XlFullScale::_8g)).unwrap();
+    block_on(sensor.gy_full_scale_set(GyFullScale::_500dps)).unwrap();
+    sensor.bus.set_main(
+        Reg::StatusReg as u8,
+        StatusReg::new()
+            .with_xlda(1)
+            .with_gda(1)
+            .with_tda(1)
+            .into_bits(),
+    );
+    set_axes(&mut sensor.bus, Reg::OutxLA as u8, [100, -200, 300]);
+    set_axes(&mut sensor.bus, Reg::OutxLG as u8, [-100, 200, -300]);
+    // 512 LSB above the 25 °C offset at 256 LSB/°C
+    let [l, h] = 512i16.to_le_bytes();
+    sensor.bus.set_main(Reg::OutTempL as u8, l);
+    sensor.bus.set_main(Reg::OutTempH as u8, h);
+
+    let sample = block_on(sensor.read_sample()).unwrap();
+
+    assert_eq!(sample.accel_mg, [100, -200, 300].map(from_fs8g_to_mg));
+    assert_eq!(
+        sample.gyro_mdps,
+        [-100, 200, -300].map(from_fs500dps_to_mdps)
+    );
+    assert_eq!(sample.temp_c, 27.0);
+}
+
+#[test]
+fn read_sample_discards_startup_samples_first() {
+    let mut sensor = sensor();
+    sensor.set_startup_discard(2);
+    block_on(sensor.xl_data_rate_set(XlDataRate::_104hzHp)).unwrap();
+    block_on(sensor.gy_data_rate_set(GyDataRate::_104hzHp)).unwrap();
+    sensor.bus.set_main(
+        Reg::StatusReg as u8,
+        StatusReg::new()
+            .with_xlda(1)
+            .with_gda(1)
+            .with_tda(1)
+            .into_bits(),
+    );
+    sensor.bus.script(MAIN, Reg::OutxLA as u8, &[1, 2, 3, 4]);
+    sensor.bus.script(MAIN, Reg::OutxLG as u8, &[5, 6, 7, 8]);
+
+    let sample = block_on(sensor.read_sample()).unwrap();
+    assert_eq!(sample.accel_mg, [3, 0, 0].map(from_fs2g_to_mg));
+    assert_eq!(sample.gyro_mdps, [7, 0, 0].map(from_fs250dps_to_mdps));
+
+    // Settled: the next call returns the next sample
+    let sample = block_on(sensor.read_sample()).unwrap();
+    assert_eq!(sample.accel_mg, [4, 0, 0].map(from_fs2g_to_mg));
+}
+
+#[test]
+fn enable_and_settle_leaves_nothing_for_read_sample_to_discard() {
+    let mut sensor = sensor();
+    sensor.bus.set_main(
+        Reg::StatusReg as u8,
+        StatusReg::new()
+            .with_xlda(1)
+            .with_gda(1)
+            .with_tda(1)
+            .into_bits(),
+    );
+    sensor.bus.script(MAIN, Reg::OutxLA as u8, &[1, 2]);
+
+    block_on(sensor.enable_and_settle(XlDataRate::_104hzHp, GyDataRate::_104hzHp)).unwrap();
+
+    let sample = block_on(sensor.read_sample()).unwrap();
+    assert_eq!(sample.accel_mg, [2, 0, 0].map(from_fs2g_to_mg));
+}
+
+#[test]
+fn liveness_requires_running_accelerometer() {
+    let mut sensor = sensor();
+
+    let result = block_on(sensor.accelerometer_liveness(4));
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+}
+
+#[test]
+fn liveness_tells_stuck_from_changing_output() {
+    let mut sensor = sensor();
+    block_on(sensor.xl_data_rate_set(XlDataRate::_104hzHp)).unwrap();
+    sensor.bus.set_main(
+        Reg::StatusReg as u8,
+        StatusReg::new().with_xlda(1).into_bits(),
+    );
+    set_axes(&mut sensor.bus, Reg::OutxLA as u8, [12, -5, 1000]);
+
+    assert!(!block_on(sensor.accelerometer_liveness(4)).unwrap());
+
+    sensor.bus.script(MAIN, Reg::OutxLA as u8, &[12, 12, 13]);
+    assert!(block_on(sensor.accelerometer_liveness(4)).unwrap());
+}
+
+#[test]
+fn acquire_into_soa_fills_one_buffer_per_axis() {
+    let mut sensor = sensor();
+    sensor.bus.set_main(
+        Reg::StatusReg as u8,
+        StatusReg::new().with_gda(1).into_bits(),
+    );
+    let out = Reg::OutxLG as u8;
+    sensor.bus.script(MAIN, out, &[1, 2, 3, 4]);
+    sensor.bus.script(MAIN, out + 2, &[11, 12, 13, 14]);
+    sensor.bus.script(MAIN, out + 4, &[0xF0, 0xF1, 0xF2, 0xF3]);
+    sensor.bus.set_main(out + 5, 0xFF);
+    let (mut x, mut y, mut z) = ([0; 4], [0; 4], [0; 4]);
+
+    let count =
+        block_on(sensor.acquire_into_soa(&mut x, &mut y, &mut z, DataReadySource::Gy)).unwrap();
+
+    assert_eq!(count, 4);
+    assert_eq!(x, [1, 2, 3, 4]);
+    assert_eq!(y, [11, 12, 13, 14]);
+    assert_eq!(z, [-16, -15, -14, -13]);
+}
+
+#[test]
+fn acquire_into_soa_rejects_temperature() {
+    let mut sensor = sensor();
+    let (mut x, mut y, mut z) = ([0; 4], [0; 4], [0; 4]);
+
+    let result = block_on(sensor.acquire_into_soa(&mut x, &mut y, &mut z, DataReadySource::Temp));
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+    assert_eq!(sensor.bus.transactions, 0);
+}
+
+#[test]
+fn read_xl_samples_returns_n_gated_samples() {
+    let mut sensor = sensor();
+    let xlda = StatusReg::new().with_xlda(1).into_bits();
+    // XLDA clear on the first poll, then set for every sample
+    sensor.bus.script(MAIN, Reg::StatusReg as u8, &[0, xlda]);
+    sensor.bus.script(MAIN, Reg::OutxLA as u8, &[1, 2, 3, 4]);
+    sensor.bus.set_main(Reg::OutzLA as u8, 9);
+
+    let samples = block_on(sensor.read_xl_samples::<4>()).unwrap();
+
+    assert_eq!(samples, [[1, 0, 9], [2, 0, 9], [3, 0, 9], [4, 0, 9]]);
+    assert_eq!(sensor.tim.calls, 1);
+}
+
+#[test]
+fn int_status_fast_is_one_status_reg_read() {
+    let mut sensor = sensor();
+    let status = StatusReg::new().with_xlda(1).with_tda(1).into_bits();
+    sensor.bus.set_main(Reg::StatusReg as u8, status);
+
+    assert_eq!(block_on(sensor.int_status_fast()).unwrap(), status);
+    assert_eq!(sensor.bus.transactions, 1);
+    assert!(sensor.bus.writes.is_empty());
+}
+
+#[test]
+fn xl_full_scale_set_safe_discards_around_the_change_with_bdu() {
+    let mut sensor = sensor();
+    block_on(sensor.block_data_update_set(1)).unwrap();
+    block_on(sensor.xl_data_rate_set(XlDataRate::_104hzHp)).unwrap();
+    sensor.bus.set_main(
+        Reg::StatusReg as u8,
+        StatusReg::new().with_xlda(1).into_bits(),
+    );
+    sensor.bus.script(MAIN, Reg::OutxLA as u8, &[1, 2, 3]);
+
+    block_on(sensor.xl_full_scale_set_safe(XlFullScale::_8g)).unwrap();
+
+    // Pending sample at the old scale and first one after the change dropped
+    assert_eq!(block_on(sensor.acceleration_raw_get()).unwrap(), [3, 0, 0]);
+    assert!(block_on(sensor.xl_full_scale_get()).unwrap() == XlFullScale::_8g);
+}
+
+#[test]
+fn xl_full_scale_set_safe_without_bdu_discards_nothing() {
+    let mut sensor = sensor();
+    block_on(sensor.xl_data_rate_set(XlDataRate::_104hzHp)).unwrap();
+    sensor.bus.script(MAIN, Reg::OutxLA as u8, &[1, 2, 3]);
+
+    block_on(sensor.xl_full_scale_set_safe(XlFullScale::_8g)).unwrap();
+
+    assert_eq!(block_on(sensor.acceleration_raw_get()).unwrap(), [1, 0, 0]);
+    assert!(block_on(sensor.xl_full_scale_get()).unwrap() == XlFullScale::_8g);
+}
+
+/// Q16.16 values must match the float path to within one LSB of the format,
+/// plus the f32 rounding of the float path itself.
+#[cfg(feature = "integer_conversions")]
+fn assert_q16_close(q16: [i32; 3], milli_units: [f32; 3]) {
+    for (q, m) in q16.iter().zip(milli_units) {
+        let expected = f64::from(m) / 1000.0 * 65536.0;
+        assert!(
+            (f64::from(*q) - expected).abs() <= 1.0 + expected.abs() * 1e-6,
+            "{q16:?} != {milli_units:?}"
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "integer_conversions")]
+fn acceleration_q16_get_matches_the_float_path() {
+    let raw = [i16::MAX, i16::MIN, -12345];
+    for fs in [
+        XlFullScale::_2g,
+        XlFullScale::_4g,
+        XlFullScale::_8g,
+        XlFullScale::_16g,
+    ] {
+        let mut sensor = sensor();
+        block_on(sensor.xl_full_scale_set(fs)).unwrap();
+        set_axes(&mut sensor.bus, Reg::OutxLA as u8, raw);
+
+        let q16 = block_on(sensor.acceleration_q16_get()).unwrap();
+
+        assert_q16_close(q16, raw.map(|lsb| from_lsb_to_mg(lsb, fs)));
+    }
+}
+
+#[test]
+#[cfg(feature = "integer_conversions")]
+fn angular_rate_q16_get_matches_the_float_path() {
+    let raw = [i16::MAX, i16::MIN, 4321];
+    for fs in [
+        GyFullScale::_125dps,
+        GyFullScale::_250dps,
+        GyFullScale::_500dps,
+        GyFullScale::_1000dps,
+        GyFullScale::_2000dps,
+    ] {
+        let mut sensor = sensor();
+        block_on(sensor.gy_full_scale_set(fs)).unwrap();
+        set_axes(&mut sensor.bus, Reg::OutxLG as u8, raw);
+
+        let q16 = block_on(sensor.angular_rate_q16_get()).unwrap();
+
+        assert_q16_close(q16, raw.map(|lsb| from_lsb_to_mdps(lsb, fs)));
+    }
+}
+
+#[test]
+fn acceleration_raw_checked_flags_an_already_read_sample() {
+    let mut sensor = sensor();
+    set_axes(&mut sensor.bus, Reg::OutxLA as u8, [10, 20, 30]);
+    sensor.bus.script(
+        MAIN,
+        Reg::StatusReg as u8,
+        &[StatusReg::new().with_xlda(1).into_bits(), 0],
+    );
+
+    assert_eq!(
+        block_on(sensor.acceleration_raw_checked()).unwrap(),
+        ([10, 20, 30], false)
+    );
+    // XLDA cleared by the read: same sample again, reported as stale
+    assert_eq!(
+        block_on(sensor.acceleration_raw_checked()).unwrap(),
+        ([10, 20, 30], true)
+    );
+}
+
+#[test]
+fn convert_xl_block_applies_the_8g_sensitivity() {
+    let raw = [[1000, -1000, 0], [4096, 2, -4096]];
+    let mut out = [[0.0; 3]; 3];
+
+    convert_xl_block(&raw, XlFullScale::_8g, &mut out);
+
+    // 0.244 mg/LSB
+    assert_close(out[0], [244.0, -244.0, 0.0]);
+    assert_close(out[1], [999.424, 0.488, -999.424]);
+    // Only as many samples as `raw` holds are converted
+    assert_eq!(out[2], [0.0; 3]);
+}
+
+#[test]
+fn convert_gy_block_applies_the_full_scale_sensitivity() {
+    let raw = [[1000, -1000, 0], [2, 4, 8]];
+    let mut out = [[0.0; 3]; 1];
+
+    convert_gy_block(&raw, GyFullScale::_2000dps, &mut out);
+
+    // 70 mdps/LSB, stopping at the end of `out`
+    assert_close(out[0], [70000.0, -70000.0, 0.0]);
+}
+
+#[test]
+fn read_sample_bdu_safe_rejects_reads_without_bdu() {
+    let mut sensor = sensor();
+    sensor.bus.set_main(
+        Reg::StatusReg as u8,
+        StatusReg::new().with_xlda(1).with_gda(1).into_bits(),
+    );
+
+    let result = block_on(sensor.read_sample_bdu_safe());
+
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+    // Only CTRL3_C was read, no output register
+    assert_eq!(sensor.bus.transactions, 1);
+}
+
+#[test]
+fn read_sample_bdu_safe_reads_with_bdu() {
+    let mut sensor = sensor();
+    block_on(sensor.block_data_update_set(1)).unwrap();
+    sensor.bus.set_main(
+        Reg::StatusReg as u8,
+        StatusReg::new()
+            .with_xlda(1)
+            .with_gda(1)
+            .with_tda(1)
+            .into_bits(),
+    );
+    set_axes(&mut sensor.bus, Reg::OutxLA as u8, [100, -200, 300]);
+
+    let sample = block_on(sensor.read_sample_bdu_safe()).unwrap();
+
+    assert_eq!(sample.accel_mg, [100, -200, 300].map(from_fs2g_to_mg));
+}
+
+#[test]
+fn gy_wake_and_read_settles_and_discards_one_sample() {
+    let mut sensor = sensor();
+    block_on(sensor.gy_data_rate_set(GyDataRate::_104hzHp)).unwrap();
+    sensor
+        .bus
+        .set_main(Reg::Ctrl4C as u8, Ctrl4C::new().with_sleep_g(1).into_bits());
+    sensor.bus.set_main(
+        Reg::StatusReg as u8,
+        StatusReg::new().with_gda(1).into_bits(),
+    );
+    sensor.bus.script(MAIN, Reg::OutxLG as u8, &[1, 2]);
+
+    let sample = block_on(sensor.gy_wake_and_read(30)).unwrap();
+
+    assert_eq!(sample, [2, 0, 0]);
+    assert_eq!(
+        Ctrl4C::from_bits(sensor.bus.main(Reg::Ctrl4C as u8)).sleep_g(),
+        0
+    );
+    // Only the settle delay: GDA is already set for both samples
+    assert_eq!(sensor.tim.calls, 1);
+    assert_eq!(sensor.tim.total_ns, 30_000_000);
+}
+
+#[test]
+fn gy_standby_keeps_the_odr_and_gy_resume_fast_waits_the_given_settle() {
+    let mut sensor = sensor();
+    block_on(sensor.gy_data_rate_set(GyDataRate::_104hzHp)).unwrap();
+
+    block_on(sensor.gy_standby()).unwrap();
+    let ctrl4_c = Ctrl4C::from_bits(sensor.bus.main(Reg::Ctrl4C as u8));
+    assert_eq!(ctrl4_c.sleep_g(), 1);
+    assert!(block_on(sensor.gy_data_rate_get()).unwrap() == GyDataRate::_104hzHp);
+    assert_eq!(sensor.tim.calls, 0);
+
+    block_on(sensor.gy_resume_fast(5)).unwrap();
+    let ctrl4_c = Ctrl4C::from_bits(sensor.bus.main(Reg::Ctrl4C as u8));
+    assert_eq!(ctrl4_c.sleep_g(), 0);
+    assert_eq!(sensor.tim.calls, 1);
+    assert_eq!(sensor.tim.total_ns, 5_000_000);
+}
+
+#[test]
+fn temperature_get_converts_to_each_scale() {
+    let mut sensor = sensor();
+    // 512 LSB above the 25 °C offset at 256 LSB/°C: 27 °C
+    let [l, h] = 512i16.to_le_bytes();
+    sensor.bus.set_main(Reg::OutTempL as u8, l);
+    sensor.bus.set_main(Reg::OutTempH as u8, h);
+
+    let cases = [
+        (TempScale::Celsius, 27.0),
+        (TempScale::Fahrenheit, 80.6),
+        (TempScale::Kelvin, 300.15),
+    ];
+    for (scale, expected) in cases {
+        let temp = block_on(sensor.temperature_get(scale)).unwrap();
+        assert!((temp - expected).abs() < 1e-3, "{temp} != {expected}");
+    }
+}
+
+#[test]
+fn stream_xl_stops_when_the_sink_returns_false() {
+    let mut sensor = sensor();
+    sensor.bus.set_main(
+        Reg::StatusReg as u8,
+        StatusReg::new().with_xlda(1).into_bits(),
+    );
+    sensor.bus.script(MAIN, Reg::OutxLA as u8, &[1, 2, 3, 4, 5]);
+
+    let mut samples = Vec::new();
+    block_on(sensor.stream_xl(|sample| {
+        samples.push(sample[0]);
+        samples.len() < 3
+    }))
+    .unwrap();
+
+    assert_eq!(samples, [1, 2, 3]);
+    // One STATUS_REG and one output read per sample
+    assert_eq!(sensor.bus.transactions, 6);
+}
+
+#[test]
+fn stream_xl_times_out_without_data_ready() {
+    let mut sensor = sensor();
+
+    let result = block_on(sensor.stream_xl(|_| panic!("no sample is ready")));
+
+    assert!(matches!(result, Err(Error::Timeout)));
+}
+
+#[test]
+#[cfg(feature = "math")]
+fn acceleration_magnitude_mg_combines_the_three_axes() {
+    let mut sensor = sensor();
+    // 16393 LSB at 0.061 mg/LSB: 1 g on a single axis
+    set_axes(&mut sensor.bus, Reg::OutxLA as u8, [0, 0, 16393]);
+    let magnitude = block_on(sensor.acceleration_magnitude_mg()).unwrap();
+    assert!((magnitude - 1000.0).abs() < 0.1, "{magnitude}");
+
+    // 3-4-5 triangle: 5000 LSB
+    set_axes(&mut sensor.bus, Reg::OutxLA as u8, [-3000, 4000, 0]);
+    let magnitude = block_on(sensor.acceleration_magnitude_mg()).unwrap();
+    assert!((magnitude - 305.0).abs() < 1e-3, "{magnitude}");
+}
+
+#[test]
+#[cfg(feature = "math")]
+fn imu_sample_assembles_si_outputs_and_timestamp() {
+    let mut sensor = sensor();
+    set_axes(&mut sensor.bus, Reg::OutxLA as u8, [1000, 0, -1000]);
+    set_axes(&mut sensor.bus, Reg::OutxLG as u8, [0, 1000, 0]);
+    let [l, h] = 512i16.to_le_bytes();
+    sensor.bus.set_main(Reg::OutTempL as u8, l);
+    sensor.bus.set_main(Reg::OutTempH as u8, h);
+
+    let sample = block_on(sensor.imu_sample()).unwrap();
+
+    // 61 mg and 8.75 dps at the default ±2 g and ±250 dps
+    let mps2 = 0.061 * 9.80665;
+    assert_close(sample.accel_mps2, [mps2, 0.0, -mps2]);
+    assert_close(sample.gyro_rads, [0.0, 8.75f32.to_radians(), 0.0]);
+    assert_eq!(sample.temp_c, 27.0);
+    assert_eq!(sample.timestamp_s, None);
+
+    // 40000 ticks of 25 us
+    block_on(sensor.timestamp_set(1)).unwrap();
+    let ts = Reg::Timestamp0 as usize;
+    sensor.bus.regs[MAIN][ts..ts + 4].copy_from_slice(&40_000u32.to_le_bytes());
+
+    let sample = block_on(sensor.imu_sample()).unwrap();
+    let timestamp_s = sample.timestamp_s.unwrap();
+    assert!((timestamp_s - 1.0).abs() < 1e-6, "{timestamp_s}");
+}
+
+#[test]
+fn gy_discard_samples_drops_n_gated_samples() {
+    let mut sensor = sensor();
+    sensor.bus.set_main(
+        Reg::StatusReg as u8,
+        StatusReg::new().with_gda(1).into_bits(),
+    );
+    sensor.bus.script(MAIN, Reg::OutxLG as u8, &[1, 2, 3]);
+
+    block_on(sensor.gy_discard_samples(0)).unwrap();
+    assert_eq!(sensor.bus.transactions, 0);
+
+    block_on(sensor.gy_discard_samples(2)).unwrap();
+    assert_eq!(block_on(sensor.angular_rate_raw_get()).unwrap(), [3, 0, 0]);
+}
+
+#[test]
+fn gy_discard_samples_times_out_without_data_ready() {
+    let mut sensor = sensor();
+
+    let result = block_on(sensor.gy_discard_samples(1));
+
+    assert!(matches!(result, Err(Error::Timeout)));
+}
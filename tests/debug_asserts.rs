@@ -0,0 +1,37 @@
+#![cfg(all(feature = "async", feature = "debug_asserts"))]
+
+mod common;
+
+use common::{ISPU, MockBus, MockDelay, block_on};
+use lsm6dso16is_rs::asynchronous::Lsm6dso16is;
+use lsm6dso16is_rs::asynchronous::prelude::*;
+
+fn sensor() -> Lsm6dso16is<MockBus, MockDelay, MainBank> {
+    Lsm6dso16is::from_bus(MockBus::new(), MockDelay::default())
+}
+
+#[test]
+#[should_panic(expected = "DRDY_PULSED_REG (0x0b) changes reserved bits")]
+fn main_bank_write_to_reserved_bits_panics() {
+    let mut sensor = sensor();
+
+    let _ = block_on(sensor.apply_register_script(&[(Reg::DrdyPulsedReg as u8, 0xFF)]));
+}
+
+#[test]
+#[should_panic(expected = "ISPU_CONFIG (0x02) changes reserved bits")]
+fn ispu_bank_write_to_reserved_bits_panics() {
+    let mut sensor = sensor();
+    sensor.bus.regs[ISPU][IspuReg::IspuConfig as usize] = 0xFF;
+
+    let _ = block_on(sensor.ispu_setup(0, 0, 0, IspuInterrupt::Pulsed));
+}
+
+#[test]
+fn ispu_bank_write_is_checked_against_the_ispu_registers() {
+    let mut sensor = sensor();
+
+    // ISPU_CONFIG shares address 0x02 with PIN_CTRL, whose reserved bits
+    // must read 1: checking against the main page would fail here.
+    block_on(sensor.ispu_setup(0, 0, 0, IspuInterrupt::Latched)).unwrap();
+}
@@ -0,0 +1,30 @@
+#![cfg(feature = "async")]
+
+mod common;
+
+use common::{MockBus, MockDelay, block_on};
+use lsm6dso16is_rs::asynchronous::{Error, Lsm6dso16is};
+
+#[test]
+fn nested_bus_error_unwraps_to_the_bus_error() {
+    let mut bus = MockBus::new();
+    bus.fail_at = Some(0);
+    let mut sensor = Lsm6dso16is::from_bus(bus, MockDelay::default());
+
+    let inner = block_on(sensor.device_id_get()).unwrap_err();
+    assert_eq!(inner.as_bus_error(), Some(&()));
+
+    // What a driver layered over the passthrough reports
+    let nested: Error<Error<()>> = Error::Bus(inner);
+    assert!(matches!(nested.as_bus_error(), Some(Error::Bus(()))));
+    assert_eq!(nested.flatten().as_bus_error(), Some(&()));
+}
+
+#[test]
+fn flatten_keeps_driver_errors() {
+    let nested: Error<Error<()>> = Error::Timeout;
+
+    let flat = nested.flatten();
+    assert!(matches!(flat, Error::Timeout));
+    assert_eq!(flat.as_bus_error(), None);
+}
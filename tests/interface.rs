@@ -0,0 +1,94 @@
+#![cfg(feature = "async")]
+
+mod common;
+
+use common::{MockDelay, NullI2c, NullSpi, RecordingI2c, block_on};
+use lsm6dso16is_rs::asynchronous::prelude::*;
+use lsm6dso16is_rs::asynchronous::{Error, I2CAddress, Interface, Lsm6dso16is};
+use st_mems_bus::asynchronous::{i2c::I2cBus, spi::SpiBus};
+
+#[test]
+fn bus_is_i2c_reports_the_bus_kind() {
+    let i2c = Lsm6dso16is::new_i2c(NullI2c, I2CAddress::I2cAddH, MockDelay::default());
+    assert!(i2c.bus_is_i2c());
+
+    let spi = Lsm6dso16is::new_spi(NullSpi, MockDelay::default());
+    assert!(!spi.bus_is_i2c());
+
+    let bus = I2cBus::new(NullI2c, I2CAddress::I2cAddL as u8);
+    assert!(Lsm6dso16is::from_bus(bus, MockDelay::default()).bus_is_i2c());
+
+    let bus = SpiBus::new(NullSpi);
+    assert!(!Lsm6dso16is::from_bus(bus, MockDelay::default()).bus_is_i2c());
+}
+
+#[test]
+fn disabling_i2c_over_an_i2c_bus_is_rejected() {
+    let mut sensor = Lsm6dso16is::new_i2c(NullI2c, I2CAddress::I2cAddH, MockDelay::default());
+    let result = block_on(sensor.ui_i2c_mode_set(UiI2cMode::Disable));
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+    block_on(sensor.ui_i2c_mode_set(UiI2cMode::Enable)).unwrap();
+
+    // Drivers built with from_bus are guarded as well
+    let bus = I2cBus::new(NullI2c, I2CAddress::I2cAddL as u8);
+    let mut sensor = Lsm6dso16is::from_bus(bus, MockDelay::default());
+    let result = block_on(sensor.ui_i2c_mode_set(UiI2cMode::Disable));
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+}
+
+#[test]
+fn disabling_i2c_over_spi_is_allowed() {
+    let mut sensor = Lsm6dso16is::new_spi(NullSpi, MockDelay::default());
+
+    block_on(sensor.ui_i2c_mode_set(UiI2cMode::Disable)).unwrap();
+}
+
+#[test]
+fn interface_matches_each_constructor() {
+    let sensor = Lsm6dso16is::new_i2c(NullI2c, I2CAddress::I2cAddH, MockDelay::default());
+    assert_eq!(sensor.interface(), Interface::I2c);
+
+    let sensor = Lsm6dso16is::new_spi(NullSpi, MockDelay::default());
+    assert_eq!(sensor.interface(), Interface::Spi);
+
+    let bus = I2cBus::new(NullI2c, I2CAddress::I2cAddL as u8);
+    let sensor = Lsm6dso16is::from_bus(bus, MockDelay::default());
+    assert_eq!(sensor.interface(), Interface::I2c);
+    assert!(sensor.bus_is_i2c());
+
+    let sensor = Lsm6dso16is::from_bus(SpiBus::new(NullSpi), MockDelay::default());
+    assert_eq!(sensor.interface(), Interface::Spi);
+    assert!(!sensor.bus_is_i2c());
+}
+
+#[test]
+fn i2c_address_converts_from_scanned_bytes() {
+    for byte in [0x6A, 0x6B] {
+        let address = I2CAddress::try_from(byte).unwrap();
+        assert_eq!(u8::from(address), byte);
+
+        let sensor = Lsm6dso16is::new_i2c(NullI2c, address, MockDelay::default());
+        assert_eq!(sensor.bus.address, byte);
+    }
+
+    assert!(I2CAddress::try_from(0x6C).is_err());
+    assert!(I2CAddress::try_from(0x00).is_err());
+}
+
+#[test]
+fn set_i2c_address_applies_to_the_following_transactions() {
+    let mut sensor = Lsm6dso16is::new_i2c(
+        RecordingI2c::default(),
+        I2CAddress::I2cAddL,
+        MockDelay::default(),
+    );
+
+    block_on(sensor.device_id_get()).unwrap();
+    sensor.set_i2c_address(I2CAddress::I2cAddH);
+    block_on(sensor.device_id_get()).unwrap();
+    block_on(sensor.xl_data_rate_set(XlDataRate::Off)).unwrap();
+
+    let addresses = &sensor.bus.i2c.addresses;
+    assert_eq!(addresses[0], 0x6A);
+    assert!(addresses[1..].iter().all(|&address| address == 0x6B));
+}
@@ -0,0 +1,561 @@
+#![cfg(feature = "async")]
+
+mod common;
+
+use common::{ISPU, MAIN, MockBus, MockDelay, block_on};
+use lsm6dso16is_rs::asynchronous::prelude::*;
+use lsm6dso16is_rs::asynchronous::{
+    Error, IspuFlags, IspuOutputField, IspuOutputReader, IspuOutputType, IspuValue, Lsm6dso16is,
+    min_xl_odr_for_ispu,
+};
+
+const FUNC_CFG_ACCESS: u8 = Reg::FuncCfgAccess as u8;
+
+fn sensor() -> Lsm6dso16is<MockBus, MockDelay, MainBank> {
+    Lsm6dso16is::from_bus(MockBus::new(), MockDelay::default())
+}
+
+fn ispu_bank_entries(bus: &MockBus) -> usize {
+    bus.writes_to(MAIN, FUNC_CFG_ACCESS)
+        .iter()
+        .filter(|data| FuncCfgAccess::from_bits(data[0]).ispu_reg_access() == 1)
+        .count()
+}
+
+#[test]
+fn ispu_setup_writes_all_registers_in_one_bank_scope() {
+    let mut sensor = sensor();
+
+    block_on(sensor.ispu_setup(
+        0x0000_0005,
+        0x0000_0001,
+        0x0000_0004,
+        IspuInterrupt::Latched,
+    ))
+    .unwrap();
+
+    let bus = &sensor.bus;
+    assert_eq!(ispu_bank_entries(bus), 1);
+    assert_eq!(
+        bus.writes_to(ISPU, IspuReg::IspuAlgo0 as u8),
+        [&[5, 0, 0, 0][..]]
+    );
+    assert_eq!(
+        bus.writes_to(ISPU, IspuReg::IspuInt1Ctrl0 as u8),
+        [&[1, 0, 0, 0][..]]
+    );
+    assert_eq!(
+        bus.writes_to(ISPU, IspuReg::IspuInt2Ctrl0 as u8),
+        [&[4, 0, 0, 0][..]]
+    );
+    let config = bus.writes_to(ISPU, IspuReg::IspuConfig as u8);
+    assert_eq!(config.len(), 1);
+    assert_eq!(IspuConfig::from_bits(config[0][0]).latched(), 1);
+    assert_eq!(bus.main(FUNC_CFG_ACCESS), 0);
+}
+
+fn image() -> Vec<u8> {
+    (0..100).map(|i| (i * 7 + 3) as u8).collect()
+}
+
+#[test]
+fn ispu_load_and_verify_accepts_a_matching_image() {
+    let mut sensor = sensor();
+    let image = image();
+
+    block_on(sensor.ispu_load_and_verify(IspuMemoryType::ProgramRamMemory, 0x1000, &image))
+        .unwrap();
+
+    assert_eq!(
+        &sensor.bus.ispu_mem[1][0x1000..0x1000 + image.len()],
+        &image[..]
+    );
+}
+
+#[test]
+fn ispu_load_and_verify_reports_a_corrupted_image() {
+    let mut sensor = sensor();
+    sensor.bus.ispu_mem_stuck = Some(0x1040);
+
+    let result =
+        block_on(sensor.ispu_load_and_verify(IspuMemoryType::ProgramRamMemory, 0x1000, &image()));
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+}
+
+#[test]
+fn ispu_load_and_verify_rejects_an_image_past_the_address_space() {
+    let mut sensor = sensor();
+
+    let result =
+        block_on(sensor.ispu_load_and_verify(IspuMemoryType::DataRamMemory, 0xFFF0, &image()));
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+    assert_eq!(sensor.bus.transactions, 0);
+}
+
+#[test]
+fn ispu_read_dout_i16_assembles_signed_words() {
+    let mut sensor = sensor();
+    // x, y, z as laid out by the norm example: -1000, 16, -32768
+    let dout = IspuReg::IspuDout00L as usize;
+    sensor.bus.regs[ISPU][dout..dout + 6].copy_from_slice(&[0x18, 0xFC, 0x10, 0x00, 0x00, 0x80]);
+
+    let mut xyz = [0i16; 3];
+    block_on(sensor.ispu_read_dout_i16(0, &mut xyz)).unwrap();
+    assert_eq!(xyz, [-1000, 16, -32768]);
+
+    // The manual reconstruction of the example agrees
+    let x = ((sensor.bus.regs[ISPU][dout + 1] as i16) << 8) + sensor.bus.regs[ISPU][dout] as i16;
+    assert_eq!(x, xyz[0]);
+    assert_eq!(sensor.bus.main(FUNC_CFG_ACCESS), 0);
+}
+
+#[test]
+fn ispu_read_dout_i16_rejects_words_past_dout_31() {
+    let mut sensor = sensor();
+
+    let mut out = [0i16; 2];
+    let result = block_on(sensor.ispu_read_dout_i16(31, &mut out));
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+    assert_eq!(sensor.bus.transactions, 0);
+}
+
+#[test]
+fn ispu_boot_complete_follows_boot_end() {
+    let mut sensor = sensor();
+    let status = IspuReg::IspuStatus as u8;
+    let boot_end = IspuStatus::new().with_boot_end(1).into_bits();
+    sensor.bus.script(ISPU, status, &[0x00, boot_end]);
+
+    assert!(!block_on(sensor.ispu_boot_complete()).unwrap());
+    assert!(block_on(sensor.ispu_boot_complete()).unwrap());
+    assert_eq!(ispu_bank_entries(&sensor.bus), 2);
+    assert_eq!(sensor.bus.main(FUNC_CFG_ACCESS), 0);
+}
+
+#[test]
+fn ispu_data_ready_follows_the_ispu_interrupt_flags() {
+    let mut sensor = sensor();
+    let status = Reg::IspuIntStatus0Mainpage as u8;
+
+    assert!(!block_on(sensor.ispu_data_ready()).unwrap());
+
+    // Algorithm 25 raised its flag (ISPU_INT_STATUS3_MAINPAGE bit 1)
+    sensor.bus.set_main(status + 3, 0x02);
+    assert!(block_on(sensor.ispu_data_ready()).unwrap());
+    assert_eq!(sensor.bus.main(status + 3), 0x02);
+    assert_eq!(ispu_bank_entries(&sensor.bus), 0);
+}
+
+#[test]
+fn min_xl_odr_for_ispu_matches_the_ispu_rate() {
+    let cases = [
+        (IspuDataRate::Off, XlDataRate::Off),
+        (IspuDataRate::_12_5hz, XlDataRate::_12_5hzHp),
+        (IspuDataRate::_104hz, XlDataRate::_104hzHp),
+        (IspuDataRate::_833hz, XlDataRate::_833hzHp),
+        (IspuDataRate::_6667hz, XlDataRate::_6667hzHp),
+    ];
+    for (ispu, xl) in cases {
+        assert!(min_xl_odr_for_ispu(ispu) == xl);
+    }
+}
+
+#[test]
+fn ensure_xl_odr_for_ispu_bumps_a_slow_accelerometer_keeping_its_mode() {
+    let mut sensor = sensor();
+    block_on(sensor.ispu_data_rate_set(IspuDataRate::_104hz)).unwrap();
+    block_on(sensor.xl_data_rate_set(XlDataRate::_26hzLp)).unwrap();
+
+    block_on(sensor.ensure_xl_odr_for_ispu()).unwrap();
+
+    assert!(block_on(sensor.xl_data_rate_get()).unwrap() == XlDataRate::_104hzLp);
+}
+
+#[test]
+fn ensure_xl_odr_for_ispu_leaves_a_fast_enough_accelerometer_alone() {
+    let mut sensor = sensor();
+    block_on(sensor.ispu_data_rate_set(IspuDataRate::_52hz)).unwrap();
+    block_on(sensor.xl_data_rate_set(XlDataRate::_208hzHp)).unwrap();
+    sensor.bus.writes.clear();
+
+    block_on(sensor.ensure_xl_odr_for_ispu()).unwrap();
+
+    assert!(sensor.bus.writes.is_empty());
+}
+
+#[test]
+fn ispu_read_all_dout_reads_the_64_byte_block_in_one_burst() {
+    let mut sensor = sensor();
+    let dout = IspuReg::IspuDout00L as usize;
+    for i in 0..64 {
+        sensor.bus.regs[ISPU][dout + i] = i as u8;
+    }
+
+    let words = block_on(sensor.ispu_read_all_dout()).unwrap();
+
+    let expected: [u16; 32] =
+        core::array::from_fn(|i| u16::from_le_bytes([2 * i as u8, 2 * i as u8 + 1]));
+    assert_eq!(words, expected);
+    assert_eq!(ispu_bank_entries(&sensor.bus), 1);
+    // Bank entry, one burst read, bank exit
+    assert_eq!(sensor.bus.transactions, 3);
+}
+
+#[test]
+fn ispu_read_memory_chunked_reads_a_region_piece_by_piece() {
+    let mut sensor = sensor();
+    let image = image();
+    sensor.bus.ispu_mem[0][0x0200..0x0200 + image.len()].copy_from_slice(&image);
+
+    let mut chunk = [0u8; 32];
+    let mut pieces = Vec::new();
+    block_on(sensor.ispu_read_memory_chunked(
+        IspuMemoryType::DataRamMemory,
+        0x0200,
+        image.len() as u16,
+        &mut chunk,
+        |offset, data| pieces.push((offset, data.to_vec())),
+    ))
+    .unwrap();
+
+    let offsets: Vec<u16> = pieces.iter().map(|(offset, _)| *offset).collect();
+    assert_eq!(offsets, [0, 32, 64, 96]);
+    assert_eq!(pieces[3].1.len(), 4);
+    let data: Vec<u8> = pieces.into_iter().flat_map(|(_, data)| data).collect();
+    assert_eq!(data, image);
+}
+
+#[test]
+fn ispu_read_memory_chunked_rejects_a_range_past_the_address_space() {
+    let mut sensor = sensor();
+
+    let mut chunk = [0u8; 32];
+    let result = block_on(sensor.ispu_read_memory_chunked(
+        IspuMemoryType::DataRamMemory,
+        0xFFF0,
+        0x20,
+        &mut chunk,
+        |_, _| panic!("nothing should be read"),
+    ));
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+    assert_eq!(sensor.bus.transactions, 0);
+}
+
+fn ispu_u32(bus: &MockBus, addr: u8) -> u32 {
+    let a = addr as usize;
+    u32::from_le_bytes(bus.regs[ISPU][a..a + 4].try_into().unwrap())
+}
+
+#[test]
+fn ispu_int1_route_bit_sets_and_clears_single_flags() {
+    let mut sensor = sensor();
+    let int1_ctrl = IspuReg::IspuInt1Ctrl0 as u8;
+
+    block_on(sensor.ispu_int1_route_bit(0, true)).unwrap();
+    block_on(sensor.ispu_int1_route_bit(29, true)).unwrap();
+    assert_eq!(ispu_u32(&sensor.bus, int1_ctrl), 1 << 29 | 1);
+    assert!(block_on(sensor.ispu_int1_route_is_set(29)).unwrap());
+
+    block_on(sensor.ispu_int1_route_bit(0, false)).unwrap();
+    assert_eq!(ispu_u32(&sensor.bus, int1_ctrl), 1 << 29);
+    assert!(!block_on(sensor.ispu_int1_route_is_set(0)).unwrap());
+    assert_eq!(ispu_u32(&sensor.bus, IspuReg::IspuInt2Ctrl0 as u8), 0);
+}
+
+#[test]
+fn ispu_int2_route_bit_sets_and_clears_single_flags() {
+    let mut sensor = sensor();
+    let int2_ctrl = IspuReg::IspuInt2Ctrl0 as u8;
+
+    block_on(sensor.ispu_int2_route_bit(7, true)).unwrap();
+    block_on(sensor.ispu_int2_route_bit(16, true)).unwrap();
+    assert_eq!(ispu_u32(&sensor.bus, int2_ctrl), 1 << 16 | 1 << 7);
+    assert!(block_on(sensor.ispu_int2_route_is_set(16)).unwrap());
+
+    block_on(sensor.ispu_int2_route_bit(16, false)).unwrap();
+    assert_eq!(ispu_u32(&sensor.bus, int2_ctrl), 1 << 7);
+    assert!(!block_on(sensor.ispu_int2_route_is_set(16)).unwrap());
+    assert_eq!(ispu_u32(&sensor.bus, IspuReg::IspuInt1Ctrl0 as u8), 0);
+}
+
+#[test]
+fn ispu_route_bits_reject_indexes_past_29() {
+    let mut sensor = sensor();
+
+    assert!(matches!(
+        block_on(sensor.ispu_int1_route_bit(30, true)),
+        Err(Error::UnexpectedValue)
+    ));
+    assert!(matches!(
+        block_on(sensor.ispu_int1_route_is_set(30)),
+        Err(Error::UnexpectedValue)
+    ));
+    assert!(matches!(
+        block_on(sensor.ispu_int2_route_bit(31, true)),
+        Err(Error::UnexpectedValue)
+    ));
+    assert!(matches!(
+        block_on(sensor.ispu_int2_route_is_set(30)),
+        Err(Error::UnexpectedValue)
+    ));
+    assert_eq!(sensor.bus.transactions, 0);
+}
+
+/// READ_MEM_EN as written to ISPU_MEM_SEL, oldest first.
+fn read_mem_en_writes(bus: &MockBus) -> Vec<u8> {
+    bus.writes_to(ISPU, IspuReg::IspuMemSel as u8)
+        .iter()
+        .map(|data| IspuMemSel::from_bits(data[0]).read_mem_en())
+        .collect()
+}
+
+#[test]
+fn ispu_read_memory_selects_the_address_once_and_leaves_read_mode() {
+    let mut sensor = sensor();
+    sensor.bus.ispu_mem[1][0x1FFE..0x2002].copy_from_slice(&[1, 2, 3, 4]);
+
+    let mut buf = [0u8; 4];
+    block_on(sensor.ispu_read_memory(IspuMemoryType::ProgramRamMemory, 0x1FFE, &mut buf, 4))
+        .unwrap();
+
+    assert_eq!(buf, [1, 2, 3, 4]);
+    assert_eq!(
+        sensor
+            .bus
+            .writes_to(ISPU, IspuReg::IspuMemAddr0 as u8)
+            .len(),
+        1
+    );
+    assert_eq!(read_mem_en_writes(&sensor.bus), [1, 0]);
+    let (mem, read_mem_en) = block_on(sensor.ispu_mem_sel_get()).unwrap();
+    assert!(mem == IspuMemoryType::ProgramRamMemory);
+    assert!(!read_mem_en);
+}
+
+#[test]
+fn ispu_read_memory_leaves_read_mode_when_the_read_fails() {
+    let mut sensor = sensor();
+    // Bank entry, ISPU_CONFIG read and write, ISPU_MEM_SEL, address, dummy
+    // read: the data read is transaction 6
+    sensor.bus.fail_at = Some(6);
+
+    let mut buf = [0u8; 4];
+    let result =
+        block_on(sensor.ispu_read_memory(IspuMemoryType::DataRamMemory, 0x0100, &mut buf, 4));
+
+    assert!(matches!(result, Err(Error::Bus(()))));
+    assert_eq!(read_mem_en_writes(&sensor.bus), [1, 0]);
+    let clk_dis: Vec<u8> = sensor
+        .bus
+        .writes_to(ISPU, IspuReg::IspuConfig as u8)
+        .iter()
+        .map(|data| IspuConfig::from_bits(data[0]).clk_dis())
+        .collect();
+    assert_eq!(clk_dis, [1, 0]);
+    assert_eq!(sensor.bus.main(FUNC_CFG_ACCESS), 0);
+}
+
+#[test]
+fn ispu_write_memory_leaves_read_mode_off() {
+    let mut sensor = sensor();
+
+    block_on(sensor.ispu_write_memory(IspuMemoryType::DataRamMemory, 0x0100, &[1, 2], 2)).unwrap();
+
+    assert_eq!(read_mem_en_writes(&sensor.bus), [0]);
+    let (mem, read_mem_en) = block_on(sensor.ispu_mem_sel_get()).unwrap();
+    assert!(mem == IspuMemoryType::DataRamMemory);
+    assert!(!read_mem_en);
+}
+
+#[test]
+fn ispu_timing_get_decodes_clock_and_rate() {
+    let mut sensor = sensor();
+    sensor.bus.set_main(
+        Reg::Ctrl10C as u8,
+        Ctrl10C::new()
+            .with_ispu_clk_sel(IspuClockSel::_10mHz as u8)
+            .with_timestamp_en(1)
+            .into_bits(),
+    );
+    sensor.bus.set_main(
+        Reg::Ctrl9C as u8,
+        Ctrl9C::new()
+            .with_ispu_rate(IspuDataRate::_833hz as u8)
+            .with_ispu_bdu(1)
+            .into_bits(),
+    );
+
+    let (clock, rate) = block_on(sensor.ispu_timing_get()).unwrap();
+
+    assert!(clock == IspuClockSel::_10mHz);
+    assert!(rate == IspuDataRate::_833hz);
+    assert!(sensor.bus.writes.is_empty());
+}
+
+#[test]
+fn configure_ispu_motion_trigger_sets_rates_and_routes_int1() {
+    let mut sensor = sensor();
+    let int1_ctrl = Int1Ctrl::new().with_int1_drdy_xl(1).into_bits();
+    sensor.bus.set_main(Reg::Int1Ctrl as u8, int1_ctrl);
+
+    block_on(sensor.configure_ispu_motion_trigger(XlDataRate::_208hzHp, IspuDataRate::_104hz))
+        .unwrap();
+
+    assert!(block_on(sensor.xl_data_rate_get()).unwrap() == XlDataRate::_208hzHp);
+    assert!(block_on(sensor.ispu_data_rate_get()).unwrap() == IspuDataRate::_104hz);
+    let md1_cfg = Md1Cfg::from_bits(sensor.bus.main(Reg::Md1Cfg as u8));
+    assert_eq!(md1_cfg.int1_ispu(), 1);
+    assert_eq!(sensor.bus.main(Reg::Int1Ctrl as u8), int1_ctrl);
+}
+
+#[test]
+fn configure_ispu_motion_trigger_rejects_a_slower_accelerometer() {
+    let mut sensor = sensor();
+
+    let result =
+        block_on(sensor.configure_ispu_motion_trigger(XlDataRate::_52hzHp, IspuDataRate::_104hz));
+
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+    assert_eq!(sensor.bus.transactions, 0);
+}
+
+#[test]
+fn ispu_output_reader_decodes_the_norm_layout() {
+    const NORM: [IspuOutputField; 4] = [
+        IspuOutputField {
+            offset: 0,
+            ty: IspuOutputType::I16,
+        },
+        IspuOutputField {
+            offset: 2,
+            ty: IspuOutputType::I16,
+        },
+        IspuOutputField {
+            offset: 4,
+            ty: IspuOutputType::I16,
+        },
+        IspuOutputField {
+            offset: 6,
+            ty: IspuOutputType::F32,
+        },
+    ];
+    let mut sensor = sensor();
+    let dout = IspuReg::IspuDout00L as usize;
+    let mut bytes = Vec::new();
+    for axis in [300i16, -400, 1200] {
+        bytes.extend_from_slice(&axis.to_le_bytes());
+    }
+    bytes.extend_from_slice(&1300.0f32.to_le_bytes());
+    sensor.bus.regs[ISPU][dout..dout + bytes.len()].copy_from_slice(&bytes);
+
+    let reader = IspuOutputReader::new(&NORM);
+    let mut out = [IspuValue::U16(0); 4];
+    block_on(reader.read(&mut sensor, &mut out)).unwrap();
+
+    assert_eq!(
+        out,
+        [
+            IspuValue::I16(300),
+            IspuValue::I16(-400),
+            IspuValue::I16(1200),
+            IspuValue::F32(1300.0),
+        ]
+    );
+    // Only the 10 bytes covered by the layout, in one burst
+    assert_eq!(sensor.bus.transactions, 3);
+}
+
+#[test]
+fn ispu_output_reader_rejects_a_field_past_dout_31_h() {
+    let layout = [IspuOutputField {
+        offset: 62,
+        ty: IspuOutputType::U32,
+    }];
+    let mut sensor = sensor();
+
+    let mut out = [IspuValue::U32(0)];
+    let result = block_on(IspuOutputReader::new(&layout).read(&mut sensor, &mut out));
+
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+    assert_eq!(sensor.bus.transactions, 0);
+}
+
+/// SW_RESET_ISPU as written to FUNC_CFG_ACCESS, oldest first.
+fn sw_reset_ispu_writes(bus: &MockBus) -> Vec<u8> {
+    bus.writes_to(MAIN, FUNC_CFG_ACCESS)
+        .iter()
+        .map(|data| FuncCfgAccess::from_bits(data[0]).sw_reset_ispu())
+        .collect()
+}
+
+#[test]
+fn ispu_reset_sequence_pulses_the_reset_and_waits_for_boot() {
+    let mut sensor = sensor();
+    let status = IspuReg::IspuStatus as u8;
+    let boot_end = IspuStatus::new().with_boot_end(1).into_bits();
+    sensor.bus.script(ISPU, status, &[0x00, 0x00, boot_end]);
+
+    block_on(sensor.ispu_reset_sequence(10)).unwrap();
+
+    assert_eq!(&sw_reset_ispu_writes(&sensor.bus)[..2], [1, 0]);
+    assert_eq!(sensor.tim.calls, 2);
+    assert_eq!(sensor.bus.main(FUNC_CFG_ACCESS), 0);
+}
+
+#[test]
+fn ispu_reset_sequence_times_out_without_boot_end() {
+    let mut sensor = sensor();
+
+    let result = block_on(sensor.ispu_reset_sequence(5));
+
+    assert!(matches!(result, Err(Error::Timeout)));
+    assert_eq!(sensor.tim.calls, 5);
+    assert_eq!(sensor.tim.total_ns, 5_000_000);
+    assert_eq!(sensor.bus.main(FUNC_CFG_ACCESS), 0);
+}
+
+#[test]
+fn ispu_algo_enabled_mask_decodes_each_bit() {
+    let mut sensor = sensor();
+    let algo = IspuReg::IspuAlgo0 as usize;
+    // Algorithms 0, 9 and 29, plus the unused bits 30 and 31
+    let mask: u32 = 1 | 1 << 9 | 1 << 29 | 0b11 << 30;
+    sensor.bus.regs[ISPU][algo..algo + 4].copy_from_slice(&mask.to_le_bytes());
+
+    let enabled = block_on(sensor.ispu_algo_enabled_mask()).unwrap();
+
+    let expected: [bool; 30] = core::array::from_fn(|n| matches!(n, 0 | 9 | 29));
+    assert_eq!(enabled, expected);
+}
+
+#[test]
+fn ispu_s2if_flags_decodes_a_known_flag_word() {
+    let mut sensor = sensor();
+    let s2if = IspuReg::IspuS2ifFlagL as usize;
+    sensor.bus.regs[ISPU][s2if..s2if + 2].copy_from_slice(&0x8005u16.to_le_bytes());
+
+    let flags = block_on(sensor.ispu_s2if_flags()).unwrap();
+
+    let set: Vec<u8> = (0..16).filter(|&n| flags.get(n)).collect();
+    assert_eq!(set, [0, 2, 15]);
+    assert!(!flags.get(16));
+}
+
+#[test]
+fn ispu_if2s_flags_set_writes_the_flag_word() {
+    let mut sensor = sensor();
+    let mut flags = IspuFlags::default();
+    flags.set(1, true);
+    flags.set(12, true);
+    flags.set(20, true);
+    flags.set(12, false);
+    flags.set(9, true);
+
+    block_on(sensor.ispu_if2s_flags_set(flags)).unwrap();
+
+    assert_eq!(flags, IspuFlags(1 << 9 | 1 << 1));
+    assert_eq!(
+        sensor.bus.writes_to(ISPU, IspuReg::IspuIf2sFlagL as u8),
+        [&[0x02, 0x02][..]]
+    );
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "async")]
+
+use lsm6dso16is_rs::asynchronous::prelude::*;
+
+#[test]
+fn xl_data_rates_lists_every_variant_once() {
+    let variants: Vec<XlDataRate> = (0..=u8::MAX)
+        .filter_map(|code| XlDataRate::try_from(code).ok())
+        .collect();
+
+    assert_eq!(XL_DATA_RATES.len(), variants.len());
+    for odr in variants {
+        assert_eq!(XL_DATA_RATES.iter().filter(|&&o| o == odr).count(), 1);
+    }
+}
+
+#[test]
+fn gy_data_rates_lists_every_variant_once() {
+    let variants: Vec<GyDataRate> = (0..=u8::MAX)
+        .filter_map(|code| GyDataRate::try_from(code).ok())
+        .collect();
+
+    assert_eq!(GY_DATA_RATES.len(), variants.len());
+    for odr in variants {
+        assert_eq!(GY_DATA_RATES.iter().filter(|&&o| o == odr).count(), 1);
+    }
+}
+
+#[test]
+fn data_rate_labels_are_unique() {
+    let xl: Vec<&str> = XL_DATA_RATES.iter().map(XlDataRate::label).collect();
+    let gy: Vec<&str> = GY_DATA_RATES.iter().map(GyDataRate::label).collect();
+
+    for labels in [xl, gy] {
+        for (i, label) in labels.iter().enumerate() {
+            assert!(!label.is_empty());
+            assert!(!labels[i + 1..].contains(label), "duplicate label {label}");
+        }
+    }
+}
@@ -0,0 +1,55 @@
+#![cfg(all(feature = "async", feature = "log"))]
+
+mod common;
+
+use std::sync::Mutex;
+
+use common::{MockBus, MockDelay, block_on};
+use lsm6dso16is_rs::asynchronous::Lsm6dso16is;
+use lsm6dso16is_rs::asynchronous::prelude::*;
+
+/// Logger keeping the driver's records for inspection.
+struct TestLogger(Mutex<Vec<(log::Level, String)>>);
+
+impl log::Log for TestLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.target().starts_with("lsm6dso16is_rs")
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            let msg = record.args().to_string();
+            self.0.lock().unwrap().push((record.level(), msg));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: TestLogger = TestLogger(Mutex::new(Vec::new()));
+
+#[test]
+fn register_accesses_are_logged() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(log::LevelFilter::Debug);
+
+    let mut bus = MockBus::new();
+    bus.set_main(Reg::WhoAmI as u8, 0x22);
+    let mut sensor = Lsm6dso16is::from_bus(bus, MockDelay::default());
+
+    block_on(sensor.device_id_get()).unwrap();
+    block_on(sensor.xl_full_scale_set(XlFullScale::_4g)).unwrap();
+
+    let records = LOGGER.0.lock().unwrap();
+    assert!(records.iter().all(|(level, _)| *level == log::Level::Debug));
+    let msgs: Vec<&str> = records.iter().map(|(_, msg)| msg.as_str()).collect();
+    let ctrl1_xl = Ctrl1Xl::new().with_fs_xl(XlFullScale::_4g as u8);
+    assert_eq!(
+        msgs,
+        [
+            "read 0x0f: [22]".to_string(),
+            "read 0x10: [00]".to_string(),
+            format!("write 0x10: [{:02x}]", ctrl1_xl.into_bits()),
+        ]
+    );
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "async")]
+
+mod common;
+
+use common::{MAIN, MockBus, MockDelay, block_on};
+use lsm6dso16is_rs::asynchronous::Lsm6dso16is;
+use lsm6dso16is_rs::asynchronous::prelude::*;
+
+const WHO_AM_I: u8 = 0x0F;
+const CTRL1_XL: u8 = 0x10;
+
+#[test]
+fn device_id_get_reads_who_am_i() {
+    let mut bus = MockBus::new();
+    bus.set_main(WHO_AM_I, 0x22);
+    let mut sensor = Lsm6dso16is::from_bus(bus, MockDelay::default());
+
+    assert_eq!(block_on(sensor.device_id_get()).unwrap(), 0x22);
+    assert_eq!(sensor.bus.transactions, 1);
+    assert!(sensor.bus.writes.is_empty());
+}
+
+#[test]
+fn scripted_reads_replay_in_order() {
+    let mut bus = MockBus::new();
+    bus.script(MAIN, WHO_AM_I, &[0x00, 0x22]);
+    let mut sensor = Lsm6dso16is::from_bus(bus, MockDelay::default());
+
+    assert_eq!(block_on(sensor.device_id_get()).unwrap(), 0x00);
+    assert_eq!(block_on(sensor.device_id_get()).unwrap(), 0x22);
+    assert_eq!(block_on(sensor.device_id_get()).unwrap(), 0x22);
+}
+
+#[test]
+fn writes_are_recorded() {
+    let mut sensor = Lsm6dso16is::from_bus(MockBus::new(), MockDelay::default());
+
+    block_on(sensor.xl_data_rate_set(XlDataRate::_104hzHp)).unwrap();
+
+    let expected = Ctrl1Xl::new()
+        .with_odr_xl(XlDataRate::_104hzHp as u8 & 0x0F)
+        .into_bits();
+    assert_eq!(sensor.bus.writes_to(MAIN, CTRL1_XL), [&[expected][..]]);
+}
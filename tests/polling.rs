@@ -0,0 +1,63 @@
+#![cfg(feature = "async")]
+
+mod common;
+
+use common::{MAIN, MockBus, MockDelay, block_on};
+use lsm6dso16is_rs::asynchronous::prelude::*;
+use lsm6dso16is_rs::asynchronous::{
+    DRDY_TIMEOUT_MS, Error, Lsm6dso16is, POLL_INTERVAL_US, SW_RESET_TIMEOUT_MS,
+};
+
+fn sensor() -> Lsm6dso16is<MockBus, MockDelay, MainBank> {
+    Lsm6dso16is::from_bus(MockBus::new(), MockDelay::default())
+}
+
+#[test]
+fn software_reset_yields_between_polls() {
+    let mut sensor = sensor();
+    let reset = Ctrl3C::new().with_sw_reset(1).into_bits();
+    sensor
+        .bus
+        .script(MAIN, Reg::Ctrl3C as u8, &[0x04, reset, reset, 0x04]);
+
+    block_on(sensor.software_reset()).unwrap();
+    assert_eq!(sensor.tim.calls, 2);
+    assert_eq!(sensor.tim.total_ns, 2 * u64::from(POLL_INTERVAL_US) * 1000);
+}
+
+#[test]
+fn software_reset_times_out_if_sw_reset_stays_set() {
+    let mut sensor = sensor();
+
+    let result = block_on(sensor.software_reset());
+    assert!(matches!(result, Err(Error::Timeout)));
+    assert_eq!(
+        sensor.tim.calls,
+        (SW_RESET_TIMEOUT_MS * 1000 / POLL_INTERVAL_US) as usize
+    );
+}
+
+#[test]
+fn data_ready_wait_polls_at_the_default_interval() {
+    let mut sensor = sensor();
+
+    let result = block_on(sensor.read_sample());
+    assert!(matches!(result, Err(Error::Timeout)));
+
+    let polls = (DRDY_TIMEOUT_MS * 1000 / POLL_INTERVAL_US) as usize;
+    assert_eq!(sensor.tim.calls, polls);
+    assert_eq!(sensor.tim.total_ns, u64::from(DRDY_TIMEOUT_MS) * 1_000_000);
+    // Two full-scale reads, then one STATUS_REG read per poll and a last one
+    assert_eq!(sensor.bus.transactions, 2 + polls + 1);
+}
+
+#[test]
+fn poll_interval_is_configurable() {
+    let mut sensor = sensor();
+    sensor.set_poll_interval_us(250);
+
+    let result = block_on(sensor.read_sample());
+    assert!(matches!(result, Err(Error::Timeout)));
+    assert_eq!(sensor.tim.calls, (DRDY_TIMEOUT_MS * 1000 / 250) as usize);
+    assert_eq!(sensor.tim.total_ns, u64::from(DRDY_TIMEOUT_MS) * 1_000_000);
+}
@@ -0,0 +1,727 @@
+#![cfg(feature = "async")]
+
+mod common;
+
+use common::{MAIN, MockBus, MockDelay, block_on};
+use lsm6dso16is_rs::asynchronous::prelude::*;
+use lsm6dso16is_rs::asynchronous::{Config, Error, ID, Lsm6dso16is, ReinitMode, SourceMask};
+
+fn sensor() -> Lsm6dso16is<MockBus, MockDelay, MainBank> {
+    Lsm6dso16is::from_bus(MockBus::new(), MockDelay::default())
+}
+
+#[test]
+fn ctrl_registers_get_reads_ctrl1_xl_to_ctrl10_c_in_order() {
+    let mut sensor = sensor();
+    for (i, addr) in (Reg::Ctrl1Xl as u8..=Reg::Ctrl10C as u8).enumerate() {
+        sensor.bus.set_main(addr, 0xA0 + i as u8);
+    }
+
+    let regs = block_on(sensor.ctrl_registers_get()).unwrap();
+
+    assert_eq!(
+        regs,
+        [0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5, 0xA6, 0x00, 0xA8, 0xA9]
+    );
+    assert_eq!(sensor.bus.transactions, 2);
+}
+
+#[test]
+fn xl_mode_consistent_accepts_modes_set_with_the_odr() {
+    let mut sensor = sensor();
+
+    block_on(sensor.xl_data_rate_set(XlDataRate::_1_6hzLp)).unwrap();
+    assert!(block_on(sensor.xl_mode_consistent()).unwrap());
+
+    block_on(sensor.xl_data_rate_set(XlDataRate::_104hzHp)).unwrap();
+    assert!(block_on(sensor.xl_mode_consistent()).unwrap());
+}
+
+#[test]
+fn xl_mode_consistent_flags_high_performance_at_1_6_hz() {
+    let mut sensor = sensor();
+    block_on(sensor.xl_data_rate_set(XlDataRate::_1_6hzLp)).unwrap();
+
+    block_on(sensor.xl_hm_mode_set(HighPerformanceMode::Enabled)).unwrap();
+
+    assert!(!block_on(sensor.xl_mode_consistent()).unwrap());
+}
+
+#[test]
+fn raw_bytes_round_trip_through_the_bus() {
+    let mut sensor = sensor();
+    let start = Reg::IspuDummyCfg1L as u8;
+
+    block_on(sensor.write_bytes(start, &[0x11, 0x22, 0x33])).unwrap();
+    assert_eq!(sensor.bus.writes_to(MAIN, start), [&[0x11, 0x22, 0x33][..]]);
+
+    let mut out = [0; 3];
+    block_on(sensor.read_bytes(start, &mut out)).unwrap();
+    assert_eq!(out, [0x11, 0x22, 0x33]);
+    assert_eq!(sensor.bus.transactions, 2);
+}
+
+#[test]
+fn read_contiguous_reads_int1_ctrl_through_ctrl7_g_in_one_burst() {
+    let mut sensor = sensor();
+    let start = Reg::Int1Ctrl as usize;
+    let expected: [u8; 10] = core::array::from_fn(|i| 0xA0 + i as u8);
+    sensor.bus.regs[MAIN][start..start + 10].copy_from_slice(&expected);
+
+    let mut out = [0; 10];
+    block_on(sensor.read_contiguous(Reg::Int1Ctrl, &mut out)).unwrap();
+
+    assert_eq!(out, expected);
+    assert_eq!(sensor.bus.transactions, 1);
+}
+
+#[test]
+fn read_contiguous_rejects_a_range_across_a_gap_without_touching_the_bus() {
+    let mut sensor = sensor();
+
+    // CTRL7_G (0x16) is followed by the reserved 0x17
+    let mut out = [0; 2];
+    let result = block_on(sensor.read_contiguous(Reg::Ctrl7G, &mut out));
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+
+    // One byte past OUTZ_H_A
+    let mut out = [0; 15];
+    let result = block_on(sensor.read_contiguous(Reg::OutTempL, &mut out));
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+
+    assert_eq!(sensor.bus.transactions, 0);
+}
+
+#[test]
+fn ispu_rate_and_bdu_decode_independently() {
+    let mut sensor = sensor();
+    block_on(sensor.ispu_data_rate_set(IspuDataRate::_104hz)).unwrap();
+    block_on(sensor.ispu_bdu_set(IspuBdu::On2b2b)).unwrap();
+
+    assert!(block_on(sensor.ispu_data_rate_get()).unwrap() == IspuDataRate::_104hz);
+    assert!(block_on(sensor.ispu_bdu_get()).unwrap() == IspuBdu::On2b2b);
+
+    let ctrl9_c = block_on(sensor.ctrl9c_get()).unwrap();
+    assert_eq!(ctrl9_c.ispu_rate(), IspuDataRate::_104hz as u8);
+    assert_eq!(ctrl9_c.ispu_bdu(), IspuBdu::On2b2b as u8);
+
+    block_on(sensor.ispu_data_rate_set(IspuDataRate::Off)).unwrap();
+    assert!(block_on(sensor.ispu_bdu_get()).unwrap() == IspuBdu::On2b2b);
+}
+
+#[test]
+fn is_125dps_follows_the_fs_125_bit() {
+    let mut sensor = sensor();
+
+    block_on(sensor.gy_full_scale_set(GyFullScale::_125dps)).unwrap();
+    assert_eq!(
+        Ctrl2G::from_bits(sensor.bus.main(Reg::Ctrl2G as u8)).fs_125(),
+        1
+    );
+    assert!(block_on(sensor.gy_full_scale_get()).unwrap().is_125dps());
+
+    block_on(sensor.gy_full_scale_set(GyFullScale::_250dps)).unwrap();
+    assert!(!block_on(sensor.gy_full_scale_get()).unwrap().is_125dps());
+}
+
+fn xl_odr(sensor: &mut Lsm6dso16is<MockBus, MockDelay, MainBank>) -> XlDataRate {
+    block_on(sensor.xl_data_rate_get()).unwrap()
+}
+
+#[test]
+fn with_temporary_xl_odr_restores_after_success() {
+    let mut sensor = sensor();
+    block_on(sensor.xl_data_rate_set(XlDataRate::_26hzLp)).unwrap();
+
+    let result = block_on(
+        sensor.with_temporary_xl_odr(XlDataRate::_833hzHp, async |s| {
+            assert!(s.xl_data_rate_get().await? == XlDataRate::_833hzHp);
+            Ok(7)
+        }),
+    );
+
+    assert!(matches!(result, Ok(7)));
+    assert!(xl_odr(&mut sensor) == XlDataRate::_26hzLp);
+}
+
+#[test]
+fn with_temporary_xl_odr_restores_after_an_error() {
+    let mut sensor = sensor();
+    block_on(sensor.xl_data_rate_set(XlDataRate::_26hzLp)).unwrap();
+
+    let result: Result<(), _> = block_on(
+        sensor.with_temporary_xl_odr(XlDataRate::_833hzHp, async |_| Err(Error::UnexpectedValue)),
+    );
+
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+    assert!(xl_odr(&mut sensor) == XlDataRate::_26hzLp);
+}
+
+#[test]
+fn low_power_wakeup_mode_writes_the_preset() {
+    let mut sensor = sensor();
+    let int1_ctrl = Int1Ctrl::new().with_int1_drdy_g(1).into_bits();
+    sensor.bus.set_main(Reg::Int1Ctrl as u8, int1_ctrl);
+    block_on(sensor.gy_data_rate_set(GyDataRate::_104hzHp)).unwrap();
+
+    block_on(sensor.low_power_wakeup_mode()).unwrap();
+
+    let bus = &sensor.bus;
+    assert_eq!(
+        Ctrl1Xl::from_bits(bus.main(Reg::Ctrl1Xl as u8)).odr_xl(),
+        0x0B
+    );
+    assert_eq!(
+        Ctrl6C::from_bits(bus.main(Reg::Ctrl6C as u8)).xl_hm_mode(),
+        1
+    );
+    assert_eq!(Ctrl2G::from_bits(bus.main(Reg::Ctrl2G as u8)).odr_g(), 0);
+    assert_eq!(
+        DrdyPulsedReg::from_bits(bus.main(Reg::DrdyPulsedReg as u8)).drdy_pulsed(),
+        1
+    );
+    let int1_ctrl = Int1Ctrl::from_bits(bus.main(Reg::Int1Ctrl as u8));
+    assert_eq!(int1_ctrl.int1_drdy_xl(), 1);
+    assert_eq!(int1_ctrl.int1_drdy_g(), 1);
+    assert!(xl_odr(&mut sensor) == XlDataRate::_1_6hzLp);
+}
+
+#[test]
+fn config_builder_matches_a_manual_config_and_applies_it() {
+    let built = Config::new()
+        .xl(XlDataRate::_104hzHp, XlFullScale::_4g)
+        .gy(GyDataRate::_208hzHp, GyFullScale::_125dps)
+        .bdu(true)
+        .int1_drdy_xl()
+        .build();
+    let manual = Config {
+        xl_odr: XlDataRate::_104hzHp,
+        xl_fs: XlFullScale::_4g,
+        gy_odr: GyDataRate::_208hzHp,
+        gy_fs: GyFullScale::_125dps,
+        bdu: true,
+        int1: PinInt1Route {
+            drdy_xl: 1,
+            ..Default::default()
+        },
+    };
+    assert!(built == manual);
+
+    let mut sensor = sensor();
+    block_on(sensor.config_apply(&built)).unwrap();
+
+    assert!(xl_odr(&mut sensor) == XlDataRate::_104hzHp);
+    assert!(block_on(sensor.xl_full_scale_get()).unwrap() == XlFullScale::_4g);
+    assert!(block_on(sensor.gy_data_rate_get()).unwrap() == GyDataRate::_208hzHp);
+    assert!(block_on(sensor.gy_full_scale_get()).unwrap().is_125dps());
+    assert_eq!(block_on(sensor.block_data_update_get()).unwrap(), 1);
+    let int1_ctrl = Int1Ctrl::from_bits(sensor.bus.main(Reg::Int1Ctrl as u8));
+    assert_eq!(int1_ctrl.int1_drdy_xl(), 1);
+    assert_eq!(int1_ctrl.int1_drdy_g(), 0);
+}
+
+#[test]
+fn gy_full_scale_round_trips_every_variant() {
+    let mut sensor = sensor();
+    let variants = [
+        (GyFullScale::_125dps, 1, 0),
+        (GyFullScale::_250dps, 0, 0),
+        (GyFullScale::_500dps, 0, 1),
+        (GyFullScale::_1000dps, 0, 2),
+        (GyFullScale::_2000dps, 0, 3),
+    ];
+
+    for (fs, fs_125, fs_g) in variants {
+        block_on(sensor.gy_full_scale_set(fs)).unwrap();
+
+        let ctrl2_g = Ctrl2G::from_bits(sensor.bus.main(Reg::Ctrl2G as u8));
+        assert_eq!((ctrl2_g.fs_125(), ctrl2_g.fs_g()), (fs_125, fs_g));
+        assert!(block_on(sensor.gy_full_scale_get()).unwrap() == fs);
+    }
+}
+
+#[test]
+fn gy_full_scale_get_ignores_fs_g_when_fs_125_is_set() {
+    let mut sensor = sensor();
+    let ctrl2_g = Ctrl2G::new().with_fs_125(1).with_fs_g(2).into_bits();
+    sensor.bus.set_main(Reg::Ctrl2G as u8, ctrl2_g);
+
+    assert!(block_on(sensor.gy_full_scale_get()).unwrap() == GyFullScale::_125dps);
+}
+
+#[test]
+fn self_test_set_checked_rejects_a_powered_down_sensor() {
+    let mut sensor = sensor();
+
+    let xl = block_on(sensor.xl_self_test_set_checked(XlSelfTest::Positive));
+    assert!(matches!(xl, Err(Error::UnexpectedValue)));
+    let gy = block_on(sensor.gy_self_test_set_checked(GySelfTest::Negative));
+    assert!(matches!(gy, Err(Error::UnexpectedValue)));
+    assert!(sensor.bus.writes_to(MAIN, Reg::Ctrl5C as u8).is_empty());
+
+    // Disabling is always allowed
+    block_on(sensor.xl_self_test_set_checked(XlSelfTest::Disable)).unwrap();
+}
+
+#[test]
+fn self_test_set_checked_accepts_a_running_sensor() {
+    let mut sensor = sensor();
+    block_on(sensor.xl_data_rate_set(XlDataRate::_52hzHp)).unwrap();
+    block_on(sensor.gy_data_rate_set(GyDataRate::_208hzHp)).unwrap();
+
+    block_on(sensor.xl_self_test_set_checked(XlSelfTest::Positive)).unwrap();
+    block_on(sensor.gy_self_test_set_checked(GySelfTest::Negative)).unwrap();
+
+    let ctrl5_c = Ctrl5C::from_bits(sensor.bus.main(Reg::Ctrl5C as u8));
+    assert_eq!(ctrl5_c.st_xl(), XlSelfTest::Positive as u8);
+    assert_eq!(ctrl5_c.st_g(), GySelfTest::Negative as u8);
+}
+
+#[test]
+fn apply_register_script_writes_the_pairs_in_order() {
+    let mut sensor = sensor();
+    let ctrl3_c = Ctrl3C::new().with_if_inc(1);
+    let script = [
+        (Reg::Ctrl3C as u8, ctrl3_c.with_h_lactive(1).into_bits()),
+        (
+            Reg::Ctrl1Xl as u8,
+            Ctrl1Xl::new().with_odr_xl(4).into_bits(),
+        ),
+        (Reg::Ctrl3C as u8, ctrl3_c.into_bits()),
+    ];
+
+    block_on(sensor.apply_register_script(&script)).unwrap();
+
+    let writes: Vec<(u8, Vec<u8>)> = sensor
+        .bus
+        .writes
+        .iter()
+        .map(|(_, reg, data)| (*reg, data.clone()))
+        .collect();
+    let expected: Vec<(u8, Vec<u8>)> = script.iter().map(|&(reg, val)| (reg, vec![val])).collect();
+    assert_eq!(writes, expected);
+}
+
+#[test]
+fn apply_register_script_rejects_non_writable_registers() {
+    let mut sensor = sensor();
+
+    // FUNC_CFG_ACCESS, WHO_AM_I, reserved 0x17, STATUS_REG
+    for reg in [0x01, 0x0F, 0x17, Reg::StatusReg as u8] {
+        let result = block_on(sensor.apply_register_script(&[(0x73, 0x5a), (reg, 0x40)]));
+        assert!(matches!(result, Err(Error::UnexpectedValue)));
+    }
+    assert_eq!(sensor.bus.transactions, 0);
+
+    block_on(sensor.apply_register_script(&[(0x73, 0x5a), (0x7A, 0xa5)])).unwrap();
+    assert_eq!(sensor.bus.main(0x73), 0x5a);
+    assert_eq!(sensor.bus.main(0x7A), 0xa5);
+}
+
+#[test]
+fn int_routes_get_decodes_both_pins() {
+    let mut sensor = sensor();
+    let bus = &mut sensor.bus;
+    bus.set_main(
+        Reg::Int1Ctrl as u8,
+        Int1Ctrl::new()
+            .with_int1_drdy_g(1)
+            .with_int1_boot(1)
+            .into_bits(),
+    );
+    bus.set_main(
+        Reg::Md1Cfg as u8,
+        Md1Cfg::new().with_int1_ispu(1).into_bits(),
+    );
+    bus.set_main(
+        Reg::Int2Ctrl as u8,
+        Int2Ctrl::new()
+            .with_int2_drdy_xl(1)
+            .with_int2_drdy_temp(1)
+            .into_bits(),
+    );
+    bus.set_main(
+        Reg::Md2Cfg as u8,
+        Md2Cfg::new().with_int2_timestamp(1).into_bits(),
+    );
+
+    let (int1, int2) = block_on(sensor.int_routes_get()).unwrap();
+
+    assert!(
+        int1 == PinInt1Route {
+            drdy_gy: 1,
+            boot: 1,
+            ispu: 1,
+            ..Default::default()
+        }
+    );
+    assert!(
+        int2 == PinInt2Route {
+            drdy_xl: 1,
+            drdy_temp: 1,
+            timestamp: 1,
+            ..Default::default()
+        }
+    );
+    assert_eq!(sensor.bus.transactions, 4);
+}
+
+#[test]
+fn status_summary_gathers_configuration_and_flags() {
+    let mut sensor = sensor();
+    block_on(sensor.xl_data_rate_set(XlDataRate::_104hzHp)).unwrap();
+    block_on(sensor.xl_full_scale_set(XlFullScale::_8g)).unwrap();
+    block_on(sensor.gy_data_rate_set(GyDataRate::_208hzHp)).unwrap();
+    block_on(sensor.gy_full_scale_set(GyFullScale::_1000dps)).unwrap();
+    block_on(sensor.block_data_update_set(1)).unwrap();
+    sensor.bus.set_main(Reg::WhoAmI as u8, ID);
+    sensor.bus.set_main(
+        Reg::StatusReg as u8,
+        StatusReg::new().with_xlda(1).with_tda(1).into_bits(),
+    );
+
+    let summary = block_on(sensor.status_summary()).unwrap();
+
+    assert_eq!(summary.who_am_i, ID);
+    assert!(summary.xl_odr == XlDataRate::_104hzHp);
+    assert!(summary.xl_fs == XlFullScale::_8g);
+    assert!(summary.gy_odr == GyDataRate::_208hzHp);
+    assert!(summary.gy_fs == GyFullScale::_1000dps);
+    assert!(summary.bdu);
+    assert!(summary.xl_drdy);
+    assert!(!summary.gy_drdy);
+    assert!(summary.temp_drdy);
+}
+
+#[test]
+fn int1_mute_zeros_the_routing_and_unmute_restores_it() {
+    let mut sensor = sensor();
+    let int1_ctrl = Int1Ctrl::new().with_int1_drdy_xl(1).into_bits();
+    let md1_cfg = Md1Cfg::new().with_int1_ispu(1).into_bits();
+    sensor.bus.set_main(Reg::Int1Ctrl as u8, int1_ctrl);
+    sensor.bus.set_main(Reg::Md1Cfg as u8, md1_cfg);
+
+    block_on(sensor.int1_mute()).unwrap();
+    assert_eq!(sensor.bus.main(Reg::Int1Ctrl as u8), 0);
+    assert_eq!(sensor.bus.main(Reg::Md1Cfg as u8), 0);
+
+    // Muting again must not overwrite the saved routing with the muted one
+    block_on(sensor.int1_mute()).unwrap();
+    block_on(sensor.int1_unmute()).unwrap();
+    assert_eq!(sensor.bus.main(Reg::Int1Ctrl as u8), int1_ctrl);
+    assert_eq!(sensor.bus.main(Reg::Md1Cfg as u8), md1_cfg);
+
+    // Unmuting when not muted leaves the bus alone
+    let transactions = sensor.bus.transactions;
+    block_on(sensor.int1_unmute()).unwrap();
+    assert_eq!(sensor.bus.transactions, transactions);
+}
+
+/// Sensor whose BOOT and SW_RESET bits clear as soon as they are read back.
+fn instant_reinit_sensor() -> Lsm6dso16is<MockBus, MockDelay, MainBank> {
+    let mut sensor = sensor();
+    let self_clearing = Ctrl3C::new().with_boot(1).with_sw_reset(1).into_bits();
+    sensor.bus.on_read = Some(Box::new(move |regs, reg| {
+        if reg == Reg::Ctrl3C as u8 {
+            regs[MAIN][reg as usize] &= !self_clearing;
+        }
+    }));
+    block_on(sensor.xl_data_rate_set(XlDataRate::_104hzHp)).unwrap();
+    block_on(sensor.gy_data_rate_set(GyDataRate::_104hzHp)).unwrap();
+    sensor.bus.writes.clear();
+    sensor
+}
+
+/// CTRL3_C writes as (BOOT, SW_RESET) pairs.
+fn ctrl3_c_commands(bus: &MockBus) -> Vec<(u8, u8)> {
+    bus.writes_to(MAIN, Reg::Ctrl3C as u8)
+        .iter()
+        .map(|data| Ctrl3C::from_bits(data[0]))
+        .map(|reg| (reg.boot(), reg.sw_reset()))
+        .collect()
+}
+
+fn assert_powered_down(sensor: &mut Lsm6dso16is<MockBus, MockDelay, MainBank>) {
+    assert!(block_on(sensor.xl_data_rate_get()).unwrap() == XlDataRate::Off);
+    assert!(block_on(sensor.gy_data_rate_get()).unwrap() == GyDataRate::Off);
+}
+
+#[test]
+fn reinitialize_software_reset_only_sets_sw_reset() {
+    let mut sensor = instant_reinit_sensor();
+
+    block_on(sensor.reinitialize(ReinitMode::SoftwareReset)).unwrap();
+
+    assert_eq!(ctrl3_c_commands(&sensor.bus), [(0, 1)]);
+    assert_powered_down(&mut sensor);
+}
+
+#[test]
+fn reinitialize_reboot_memory_only_sets_boot() {
+    let mut sensor = instant_reinit_sensor();
+
+    block_on(sensor.reinitialize(ReinitMode::RebootMemory)).unwrap();
+
+    assert_eq!(ctrl3_c_commands(&sensor.bus), [(1, 0)]);
+    // The 10 ms reboot time is waited before BOOT is polled
+    assert_eq!(sensor.tim.calls, 1);
+    assert_eq!(sensor.tim.total_ns, 10_000_000);
+    assert_powered_down(&mut sensor);
+}
+
+#[test]
+fn reinitialize_both_reboots_then_resets() {
+    let mut sensor = instant_reinit_sensor();
+
+    block_on(sensor.reinitialize(ReinitMode::Both)).unwrap();
+
+    assert_eq!(ctrl3_c_commands(&sensor.bus), [(1, 0), (0, 1)]);
+    assert_powered_down(&mut sensor);
+}
+
+#[test]
+fn ctrl9c_and_ctrl10c_getters_decode_known_bytes() {
+    let mut sensor = sensor();
+    let ctrl9_c = Ctrl9C::new()
+        .with_ispu_bdu(IspuBdu::On2b2b as u8)
+        .with_ispu_rate(IspuDataRate::_208hz as u8);
+    let ctrl10_c = Ctrl10C::new()
+        .with_ispu_clk_sel(IspuClockSel::_10mHz as u8)
+        .with_timestamp_en(1);
+    sensor.bus.set_main(Reg::Ctrl9C as u8, ctrl9_c.into_bits());
+    sensor
+        .bus
+        .set_main(Reg::Ctrl10C as u8, ctrl10_c.into_bits());
+
+    assert_eq!(
+        block_on(sensor.ctrl9c_raw_get()).unwrap(),
+        ctrl9_c.into_bits()
+    );
+    let parsed = block_on(sensor.ctrl9c_get()).unwrap();
+    assert_eq!(parsed.ispu_rate(), IspuDataRate::_208hz as u8);
+    assert_eq!(parsed.ispu_bdu(), IspuBdu::On2b2b as u8);
+
+    assert_eq!(
+        block_on(sensor.ctrl10c_raw_get()).unwrap(),
+        ctrl10_c.into_bits()
+    );
+    let parsed = block_on(sensor.ctrl10c_get()).unwrap();
+    assert_eq!(parsed.ispu_clk_sel(), IspuClockSel::_10mHz as u8);
+    assert_eq!(parsed.timestamp_en(), 1);
+}
+
+#[test]
+fn int1_ctrl_get_and_int2_ctrl_get_decode_each_register() {
+    let mut sensor = sensor();
+    sensor.bus.set_main(
+        Reg::Int1Ctrl as u8,
+        Int1Ctrl::new()
+            .with_int1_drdy_g(1)
+            .with_int1_boot(1)
+            .into_bits(),
+    );
+    sensor.bus.set_main(
+        Reg::Int2Ctrl as u8,
+        Int2Ctrl::new()
+            .with_int2_drdy_xl(1)
+            .with_int2_sleep_ispu(1)
+            .into_bits(),
+    );
+
+    let int1 = block_on(sensor.int1_ctrl_get()).unwrap();
+    assert_eq!(
+        (int1.int1_drdy_xl(), int1.int1_drdy_g(), int1.int1_boot()),
+        (0, 1, 1)
+    );
+
+    let int2 = block_on(sensor.int2_ctrl_get()).unwrap();
+    assert_eq!(
+        (
+            int2.int2_drdy_xl(),
+            int2.int2_drdy_g(),
+            int2.int2_drdy_temp(),
+            int2.int2_sleep_ispu()
+        ),
+        (1, 0, 0, 1)
+    );
+}
+
+#[test]
+fn with_low_power_switches_both_sensors_and_restores_their_modes() {
+    let mut sensor = sensor();
+    block_on(sensor.xl_data_rate_set(XlDataRate::_208hzHp)).unwrap();
+    block_on(sensor.gy_data_rate_set(GyDataRate::_52hzHp)).unwrap();
+
+    let result = block_on(sensor.with_low_power(async |s| {
+        assert!(s.xl_data_rate_get().await? == XlDataRate::_208hzLp);
+        assert!(s.gy_data_rate_get().await? == GyDataRate::_52hzLp);
+        Ok(3)
+    }));
+
+    assert!(matches!(result, Ok(3)));
+    assert!(xl_odr(&mut sensor) == XlDataRate::_208hzHp);
+    assert!(block_on(sensor.gy_data_rate_get()).unwrap() == GyDataRate::_52hzHp);
+}
+
+#[test]
+fn with_low_power_keeps_a_sensor_that_is_off_and_restores_after_an_error() {
+    let mut sensor = sensor();
+    block_on(sensor.xl_data_rate_set(XlDataRate::_104hzHp)).unwrap();
+
+    let result: Result<(), _> = block_on(sensor.with_low_power(async |s| {
+        assert!(s.gy_data_rate_get().await? == GyDataRate::Off);
+        Err(Error::UnexpectedValue)
+    }));
+
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+    assert!(xl_odr(&mut sensor) == XlDataRate::_104hzHp);
+    assert!(block_on(sensor.gy_data_rate_get()).unwrap() == GyDataRate::Off);
+}
+
+#[test]
+fn pin_ctrl_get_reports_the_sdo_pull_up() {
+    let mut sensor = sensor();
+    assert_eq!(block_on(sensor.pin_ctrl_get()).unwrap().sdo_pu_en(), 0);
+
+    let pulled_up = PinCtrl::new().with_sdo_pu_en(1).into_bits();
+    sensor.bus.set_main(Reg::PinCtrl as u8, pulled_up);
+    let pin_ctrl = block_on(sensor.pin_ctrl_get()).unwrap();
+    assert_eq!(pin_ctrl.sdo_pu_en(), 1);
+    assert_eq!(pin_ctrl.into_bits(), pulled_up);
+    assert!(sensor.bus.writes.is_empty());
+}
+
+#[test]
+fn odr_both_at_52_hz_selects_the_52_hz_high_performance_variants() {
+    let mut sensor = sensor();
+    block_on(sensor.xl_data_rate_set(XlDataRate::_208hzLp)).unwrap();
+    block_on(sensor.gy_data_rate_set(GyDataRate::_26hzLp)).unwrap();
+
+    block_on(sensor.odr_both_at(52.0)).unwrap();
+
+    assert!(xl_odr(&mut sensor) == XlDataRate::_52hzHp);
+    assert!(block_on(sensor.gy_data_rate_get()).unwrap() == GyDataRate::_52hzHp);
+    let (xl, gy) = block_on(sensor.odr_pair_get()).unwrap();
+    assert!(xl == XlDataRate::_52hzHp);
+    assert!(gy == GyDataRate::_52hzHp);
+}
+
+#[test]
+fn odr_pair_set_writes_both_sensors_in_two_bursts() {
+    let mut sensor = sensor();
+    block_on(sensor.odr_pair_set(XlDataRate::_104hzLp, GyDataRate::_26hzHp)).unwrap();
+
+    assert_eq!(sensor.bus.transactions, 3);
+    assert_eq!(sensor.bus.writes.len(), 2);
+    assert!(xl_odr(&mut sensor) == XlDataRate::_104hzLp);
+    assert!(block_on(sensor.gy_data_rate_get()).unwrap() == GyDataRate::_26hzHp);
+}
+
+#[test]
+fn odr_both_at_rejects_rates_above_6667_hz_without_writing() {
+    let mut sensor = sensor();
+    let result = block_on(sensor.odr_both_at(7000.0));
+
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+    assert!(sensor.bus.writes.is_empty());
+}
+
+#[test]
+fn strict_getters_reject_undefined_encodings_the_lenient_ones_default() {
+    let mut sensor = sensor();
+    // ODR_XL 0b1011 has no variant
+    sensor.bus.set_main(
+        Reg::Ctrl1Xl as u8,
+        Ctrl1Xl::new().with_odr_xl(0xB).into_bits(),
+    );
+    sensor.bus.set_main(
+        Reg::Ctrl5C as u8,
+        Ctrl5C::new().with_st_xl(0b11).into_bits(),
+    );
+
+    assert!(matches!(
+        block_on(sensor.xl_data_rate_get_strict()),
+        Err(Error::UnexpectedValue)
+    ));
+    assert!(xl_odr(&mut sensor) == XlDataRate::Off);
+    assert!(matches!(
+        block_on(sensor.xl_self_test_get_strict()),
+        Err(Error::UnexpectedValue)
+    ));
+    assert!(block_on(sensor.xl_self_test_get()).unwrap() == XlSelfTest::Disable);
+
+    sensor.bus.set_main(
+        Reg::Ctrl1Xl as u8,
+        Ctrl1Xl::new().with_odr_xl(0x4).into_bits(),
+    );
+    assert!(block_on(sensor.xl_data_rate_get_strict()).unwrap() == XlDataRate::_104hzHp);
+}
+
+#[test]
+fn route_sources_maps_each_mask_to_its_pin_registers() {
+    let mut sensor = sensor();
+    let to_int1 = SourceMask {
+        drdy_xl: true,
+        sh_endop: true,
+        boot: true,
+        ..Default::default()
+    };
+    let to_int2 = SourceMask {
+        drdy_gy: true,
+        drdy_temp: true,
+        timestamp: true,
+        ispu: true,
+        ..Default::default()
+    };
+
+    block_on(sensor.route_sources(to_int1, to_int2)).unwrap();
+
+    let int1_ctrl = Int1Ctrl::new()
+        .with_int1_drdy_xl(1)
+        .with_int1_boot(1)
+        .into_bits();
+    let md1_cfg = Md1Cfg::new().with_int1_shub(1).into_bits();
+    let int2_ctrl = Int2Ctrl::new()
+        .with_int2_drdy_g(1)
+        .with_int2_drdy_temp(1)
+        .into_bits();
+    let md2_cfg = Md2Cfg::new()
+        .with_int2_timestamp(1)
+        .with_int2_ispu(1)
+        .into_bits();
+    assert_eq!(
+        sensor.bus.writes_to(MAIN, Reg::Int1Ctrl as u8),
+        [&[int1_ctrl][..]]
+    );
+    assert_eq!(
+        sensor.bus.writes_to(MAIN, Reg::Md1Cfg as u8),
+        [&[md1_cfg][..]]
+    );
+    assert_eq!(
+        sensor.bus.writes_to(MAIN, Reg::Int2Ctrl as u8),
+        [&[int2_ctrl][..]]
+    );
+    assert_eq!(
+        sensor.bus.writes_to(MAIN, Reg::Md2Cfg as u8),
+        [&[md2_cfg][..]]
+    );
+}
+
+#[test]
+fn route_sources_rejects_a_source_on_the_wrong_pin_without_writing() {
+    let mut sensor = sensor();
+    let int2_boot = SourceMask {
+        boot: true,
+        ..Default::default()
+    };
+    let int1_temp = SourceMask {
+        drdy_temp: true,
+        ..Default::default()
+    };
+
+    let result = block_on(sensor.route_sources(SourceMask::default(), int2_boot));
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+    let result = block_on(sensor.route_sources(int1_temp, SourceMask::default()));
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+    assert!(sensor.bus.writes.is_empty());
+}
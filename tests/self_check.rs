@@ -0,0 +1,75 @@
+#![cfg(feature = "async")]
+
+mod common;
+
+use common::{MAIN, MockBus, MockDelay, block_on};
+use lsm6dso16is_rs::asynchronous::prelude::*;
+use lsm6dso16is_rs::asynchronous::{Error, ID, Lsm6dso16is};
+
+/// Device answering WHO_AM_I whose software reset completes on the first
+/// poll and leaves CTRL3_C at its 0x04 default.
+fn healthy() -> Lsm6dso16is<MockBus, MockDelay, MainBank> {
+    let mut bus = MockBus::new();
+    bus.set_main(Reg::WhoAmI as u8, ID);
+    bus.script(MAIN, Reg::Ctrl3C as u8, &[0x04, 0x04]);
+    Lsm6dso16is::from_bus(bus, MockDelay::default())
+}
+
+#[test]
+fn power_on_self_check_passes_on_reset_defaults() {
+    let mut sensor = healthy();
+
+    block_on(sensor.power_on_self_check()).unwrap();
+    let ctrl3_c = sensor.bus.writes_to(MAIN, Reg::Ctrl3C as u8);
+    assert_eq!(Ctrl3C::from_bits(ctrl3_c[0][0]).sw_reset(), 1);
+}
+
+#[test]
+fn power_on_self_check_reports_the_mismatched_default() {
+    let mut sensor = healthy();
+    let ctrl2_g = Ctrl2G::new()
+        .with_fs_g(GyFullScale::_1000dps as u8)
+        .into_bits();
+    sensor.bus.set_main(Reg::Ctrl2G as u8, ctrl2_g);
+
+    let result = block_on(sensor.power_on_self_check());
+    assert!(matches!(
+        result,
+        Err(Error::RegisterMismatch { reg: 0x11, expected: 0x00, actual })
+            if actual == ctrl2_g
+    ));
+}
+
+#[test]
+fn power_on_self_check_reports_a_wrong_device_id() {
+    let mut sensor = healthy();
+    sensor.bus.set_main(Reg::WhoAmI as u8, 0x6C);
+
+    let result = block_on(sensor.power_on_self_check());
+    assert!(matches!(
+        result,
+        Err(Error::RegisterMismatch {
+            reg: 0x0F,
+            expected: ID,
+            actual: 0x6C
+        })
+    ));
+    assert!(sensor.bus.writes.is_empty());
+}
+
+#[test]
+fn is_ready_waits_for_who_am_i_and_boot() {
+    let mut sensor = Lsm6dso16is::from_bus(MockBus::new(), MockDelay::default());
+    // Not answering yet
+    assert!(!block_on(sensor.is_ready()).unwrap());
+
+    sensor.bus.set_main(Reg::WhoAmI as u8, ID);
+    sensor
+        .bus
+        .set_main(Reg::Ctrl3C as u8, Ctrl3C::new().with_boot(1).into_bits());
+    assert!(!block_on(sensor.is_ready()).unwrap());
+
+    sensor.bus.set_main(Reg::Ctrl3C as u8, 0x04);
+    assert!(block_on(sensor.is_ready()).unwrap());
+    assert!(sensor.bus.writes.is_empty());
+}
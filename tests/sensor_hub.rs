@@ -0,0 +1,489 @@
+#![cfg(feature = "async")]
+
+mod common;
+
+use common::{MAIN, MockBus, MockDelay, SENSOR_HUB, block_on};
+use lsm6dso16is_rs::asynchronous::prelude::*;
+use lsm6dso16is_rs::asynchronous::{Error, Lsm6dso16is};
+
+fn sensor() -> Lsm6dso16is<MockBus, MockDelay, MainBank> {
+    Lsm6dso16is::from_bus(MockBus::new(), MockDelay::default())
+}
+
+const MASTER_CONFIG: u8 = SensHubReg::MasterConfig as u8;
+
+/// MASTER_ON as written to MASTER_CONFIG, oldest first.
+fn master_on_writes(bus: &MockBus) -> Vec<u8> {
+    bus.writes_to(SENSOR_HUB, MASTER_CONFIG)
+        .iter()
+        .map(|data| MasterConfig::from_bits(data[0]).master_on())
+        .collect()
+}
+
+/// ODR_XL as written to CTRL1_XL, oldest first.
+fn odr_xl_writes(bus: &MockBus) -> Vec<u8> {
+    bus.writes_to(MAIN, Reg::Ctrl1Xl as u8)
+        .iter()
+        .map(|data| Ctrl1Xl::from_bits(data[0]).odr_xl())
+        .collect()
+}
+
+fn endop() -> u8 {
+    StatusMasterMainpage::new()
+        .with_sens_hub_endop(1)
+        .into_bits()
+}
+
+/// Sensor with the accelerometer running at 104 Hz before the hub cycle.
+fn running_sensor() -> Lsm6dso16is<MockBus, MockDelay, MainBank> {
+    let mut sensor = sensor();
+    block_on(sensor.xl_data_rate_set(XlDataRate::_104hzHp)).unwrap();
+    sensor.bus.writes.clear();
+    sensor
+}
+
+const WRITE: ShCfgWrite = ShCfgWrite {
+    slv0_add: 0x1E,
+    slv0_subadd: 0x20,
+    slv0_data: 0x5A,
+};
+
+#[test]
+fn sh_slave_connected_count_is_one_more_than_the_encoding() {
+    assert_eq!(ShSlaveConnected::_0.count(), 1);
+    assert_eq!(ShSlaveConnected::_01.count(), 2);
+    assert_eq!(ShSlaveConnected::_012.count(), 3);
+    assert_eq!(ShSlaveConnected::_0123.count(), 4);
+}
+
+#[test]
+fn sh_read_data_raw_get_reads_the_whole_output_window() {
+    let mut sensor = sensor();
+    let first = SensHubReg::SensorHub1 as usize;
+    for i in 0..SENSOR_HUB_REGISTER_COUNT {
+        sensor.bus.regs[SENSOR_HUB][first + i] = i as u8 + 1;
+    }
+
+    let mut val = [0; SENSOR_HUB_REGISTER_COUNT];
+    block_on(sensor.sh_read_data_raw_get(&mut val)).unwrap();
+    assert_eq!(val, core::array::from_fn(|i| i as u8 + 1));
+}
+
+#[test]
+fn sh_read_data_raw_get_rejects_reads_past_sensor_hub_18() {
+    let mut sensor = sensor();
+
+    let mut val = [0; SENSOR_HUB_REGISTER_COUNT + 1];
+    let result = block_on(sensor.sh_read_data_raw_get(&mut val));
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+    assert_eq!(sensor.bus.transactions, 0);
+}
+
+#[cfg(feature = "passthrough")]
+#[test]
+fn passthrough_rejects_read_length_before_touching_the_bus() {
+    use lsm6dso16is_rs::asynchronous::Lsm6dso16isPassthrough;
+    use st_mems_bus::asynchronous::BusOperation;
+
+    let mut sensor = sensor();
+    for len in [0, 8] {
+        let mut pass = Lsm6dso16isPassthrough::new_from_sensor(&mut sensor, 0x1E);
+        let mut rbuf = [0u8; 8];
+        let result = block_on(pass.write_byte_read_bytes(&[0x68], &mut rbuf[..len]));
+        assert!(matches!(result, Err(Error::UnexpectedValue)));
+    }
+    assert_eq!(sensor.bus.transactions, 0);
+}
+
+#[test]
+fn sh_write_once_waits_for_endop_and_restores_the_trigger() {
+    let mut sensor = running_sensor();
+    let status = Reg::StatusMasterMainpage as u8;
+    sensor.bus.script(MAIN, status, &[0x00, 0x00, endop()]);
+
+    block_on(sensor.sh_write_once(WRITE)).unwrap();
+
+    assert_eq!(sensor.tim.calls, 2);
+    assert_eq!(
+        sensor
+            .bus
+            .writes_to(SENSOR_HUB, SensHubReg::DatawriteSlv0 as u8),
+        [&[0x5A][..]]
+    );
+    assert_eq!(master_on_writes(&sensor.bus), [1, 0]);
+    let xl_104hz = XlDataRate::_104hzHp as u8;
+    assert_eq!(
+        odr_xl_writes(&sensor.bus),
+        [0, XlDataRate::_26hzHp as u8, xl_104hz]
+    );
+}
+
+#[test]
+fn sh_write_once_cleans_up_when_enabling_the_master_fails() {
+    let mut sensor = running_sensor();
+    sensor.bus.fail_write = Some((SENSOR_HUB, MASTER_CONFIG));
+
+    let result = block_on(sensor.sh_write_once(WRITE));
+
+    assert!(matches!(result, Err(Error::Bus(()))));
+    assert_eq!(master_on_writes(&sensor.bus), [0]);
+    assert_eq!(odr_xl_writes(&sensor.bus), [0, XlDataRate::_104hzHp as u8]);
+    assert_eq!(sensor.bus.main(Reg::FuncCfgAccess as u8), 0);
+}
+
+#[test]
+fn sh_write_once_cleans_up_after_a_timeout() {
+    let mut sensor = running_sensor();
+
+    let result = block_on(sensor.sh_write_once(WRITE));
+
+    assert!(matches!(result, Err(Error::Timeout)));
+    assert_eq!(master_on_writes(&sensor.bus).last(), Some(&0));
+    assert_eq!(
+        odr_xl_writes(&sensor.bus).last(),
+        Some(&(XlDataRate::_104hzHp as u8))
+    );
+}
+
+#[test]
+fn sh_probe_slaves_reports_a_nacking_slave() {
+    let mut sensor = running_sensor();
+    block_on(sensor.sh_slave_connected_set(ShSlaveConnected::_012)).unwrap();
+    let status = StatusMasterMainpage::new()
+        .with_sens_hub_endop(1)
+        .with_slave1_nack(1)
+        .into_bits();
+    sensor.bus.set_main(Reg::StatusMasterMainpage as u8, status);
+
+    let answered = block_on(sensor.sh_probe_slaves()).unwrap();
+
+    // Slave 3 is not configured, so it is never reported as answering
+    assert_eq!(answered, [true, false, true, false]);
+    assert_eq!(master_on_writes(&sensor.bus).last(), Some(&0));
+    assert_eq!(
+        odr_xl_writes(&sensor.bus).last(),
+        Some(&(XlDataRate::_104hzHp as u8))
+    );
+}
+
+#[test]
+fn sh_probe_slaves_reports_none_when_the_cycle_times_out() {
+    let mut sensor = running_sensor();
+    block_on(sensor.sh_slave_connected_set(ShSlaveConnected::_0123)).unwrap();
+
+    let answered = block_on(sensor.sh_probe_slaves()).unwrap();
+
+    assert_eq!(answered, [false; 4]);
+    assert_eq!(master_on_writes(&sensor.bus).last(), Some(&0));
+}
+
+/// Sensor whose hub cycles complete at once: XLDA and SENS_HUB_ENDOP set.
+#[cfg(feature = "passthrough")]
+fn completing_sensor() -> Lsm6dso16is<MockBus, MockDelay, MainBank> {
+    let mut sensor = sensor();
+    sensor
+        .bus
+        .set_main(Reg::StatusMasterMainpage as u8, endop());
+    sensor.bus.set_main(
+        Reg::StatusReg as u8,
+        StatusReg::new().with_xlda(1).into_bits(),
+    );
+    sensor
+}
+
+/// Fail each bus transaction of `transfer` in turn and check that the hub
+/// is always left stopped: master off, accelerometer off, main bank.
+/// Returns how many of the injected errors were reported.
+#[cfg(feature = "passthrough")]
+fn assert_cleanup_at_every_step(
+    transfer: impl Fn(&mut Lsm6dso16is<MockBus, MockDelay, MainBank>) -> Result<(), Error<()>>,
+) -> usize {
+    let mut sensor = completing_sensor();
+    transfer(&mut sensor).unwrap();
+    let steps = sensor.bus.transactions;
+
+    let mut reported = 0;
+    for step in 0..steps {
+        let mut sensor = completing_sensor();
+        sensor.bus.fail_at = Some(step);
+
+        // Some steps, like the dummy output read, may fail without
+        // aborting the transfer; either way the hub must end up stopped.
+        if transfer(&mut sensor).is_err() {
+            reported += 1;
+        }
+        let master_config = MasterConfig::from_bits(sensor.bus.sensor_hub(MASTER_CONFIG));
+        assert_eq!(master_config.master_on(), 0, "step {step}");
+        let ctrl1_xl = Ctrl1Xl::from_bits(sensor.bus.main(Reg::Ctrl1Xl as u8));
+        assert_eq!(ctrl1_xl.odr_xl(), 0, "step {step}");
+        assert_eq!(sensor.bus.main(Reg::FuncCfgAccess as u8), 0, "step {step}");
+    }
+
+    reported
+}
+
+#[cfg(feature = "passthrough")]
+#[test]
+fn passthrough_read_cleans_up_after_a_mid_sequence_error() {
+    use lsm6dso16is_rs::asynchronous::Lsm6dso16isPassthrough;
+    use st_mems_bus::asynchronous::BusOperation;
+
+    let reported = assert_cleanup_at_every_step(|sensor| {
+        let mut pass = Lsm6dso16isPassthrough::new_from_sensor(sensor, 0x1E);
+        let mut rbuf = [0u8; 2];
+        block_on(pass.write_byte_read_bytes(&[0x68], &mut rbuf))
+    });
+    assert!(reported > 0);
+}
+
+#[cfg(feature = "passthrough")]
+#[test]
+fn passthrough_write_cleans_up_after_a_mid_sequence_error() {
+    use lsm6dso16is_rs::asynchronous::Lsm6dso16isPassthrough;
+    use st_mems_bus::asynchronous::BusOperation;
+
+    let reported = assert_cleanup_at_every_step(|sensor| {
+        let mut pass = Lsm6dso16isPassthrough::new_from_sensor(sensor, 0x1E);
+        block_on(pass.write_bytes(&[0x20, 0x5A, 0x5B]))
+    });
+    assert!(reported > 0);
+}
+
+#[test]
+fn sh_status_raw_get_returns_the_status_master_byte() {
+    let mut sensor = sensor();
+    let status = StatusMasterMainpage::new()
+        .with_sens_hub_endop(1)
+        .with_slave2_nack(1)
+        .with_wr_once_done(1)
+        .into_bits();
+    sensor.bus.set_main(Reg::StatusMasterMainpage as u8, status);
+
+    assert_eq!(block_on(sensor.sh_status_raw_get()).unwrap(), status);
+    let parsed = block_on(sensor.sh_status_get()).unwrap();
+    assert_eq!(parsed.into_bits(), status);
+    assert_eq!(sensor.bus.transactions, 2);
+}
+
+#[test]
+fn target_configuration_is_rejected_while_the_master_is_on() {
+    let mut sensor = sensor();
+    block_on(sensor.sh_master_set(1)).unwrap();
+    sensor.bus.writes.clear();
+
+    let result = block_on(sensor.sh_cfg_write(WRITE));
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+
+    let read = ShCfgRead {
+        slv_add: 0x1E,
+        slv_subadd: 0x68,
+        slv_len: 6,
+    };
+    let result = block_on(sensor.sh_slv_cfg_read(1, &read));
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+
+    // Only bank switches, no target register written
+    assert!(
+        sensor
+            .bus
+            .writes
+            .iter()
+            .all(|(_, addr, _)| *addr == Reg::FuncCfgAccess as u8)
+    );
+    assert_eq!(sensor.bus.main(Reg::FuncCfgAccess as u8), 0);
+}
+
+#[test]
+fn target_configuration_is_accepted_while_the_master_is_off() {
+    let mut sensor = sensor();
+
+    block_on(sensor.sh_cfg_write(WRITE)).unwrap();
+
+    assert_eq!(
+        sensor
+            .bus
+            .writes_to(SENSOR_HUB, SensHubReg::DatawriteSlv0 as u8),
+        [&[0x5A][..]]
+    );
+}
+
+#[cfg(feature = "passthrough")]
+#[test]
+fn passthrough_with_zero_delays_never_waits() {
+    use lsm6dso16is_rs::asynchronous::Lsm6dso16isPassthrough;
+    use st_mems_bus::asynchronous::BusOperation;
+
+    let mut sensor = completing_sensor();
+    let xlda = StatusReg::new().with_xlda(1).into_bits();
+    sensor.bus.script(MAIN, Reg::StatusReg as u8, &[0, 0, xlda]);
+    sensor
+        .bus
+        .script(MAIN, Reg::StatusMasterMainpage as u8, &[0, endop()]);
+
+    let mut pass = Lsm6dso16isPassthrough::new_from_sensor(&mut sensor, 0x1E).with_delays(0, 0);
+    let mut rbuf = [0u8; 2];
+    block_on(pass.write_byte_read_bytes(&[0x68], &mut rbuf)).unwrap();
+    block_on(pass.write_bytes(&[0x20, 0x5A])).unwrap();
+
+    assert_eq!(sensor.tim.calls, 0);
+}
+
+#[cfg(feature = "passthrough")]
+#[test]
+fn passthrough_with_zero_delays_still_times_out() {
+    use lsm6dso16is_rs::asynchronous::{DRDY_TIMEOUT_MS, Lsm6dso16isPassthrough, POLL_INTERVAL_US};
+    use st_mems_bus::asynchronous::BusOperation;
+
+    let mut sensor = sensor();
+    let mut pass = Lsm6dso16isPassthrough::new_from_sensor(&mut sensor, 0x1E).with_delays(0, 0);
+    let mut rbuf = [0u8; 2];
+    let result = block_on(pass.write_byte_read_bytes(&[0x68], &mut rbuf));
+
+    assert!(matches!(result, Err(Error::Timeout)));
+    assert_eq!(sensor.tim.calls, 0);
+    let polls = (DRDY_TIMEOUT_MS * 1000 / POLL_INTERVAL_US) as usize;
+    assert!(sensor.bus.transactions > polls);
+    assert!(sensor.bus.transactions < 2 * polls);
+}
+
+#[cfg(feature = "passthrough")]
+#[test]
+fn passthrough_waits_the_default_settle_delay_between_polls() {
+    use lsm6dso16is_rs::asynchronous::{Lsm6dso16isPassthrough, PASSTHROUGH_SETTLE_MS};
+    use st_mems_bus::asynchronous::BusOperation;
+
+    let mut sensor = completing_sensor();
+    let xlda = StatusReg::new().with_xlda(1).into_bits();
+    sensor.bus.script(MAIN, Reg::StatusReg as u8, &[0, xlda]);
+
+    let mut pass = Lsm6dso16isPassthrough::new_from_sensor(&mut sensor, 0x1E);
+    let mut rbuf = [0u8; 2];
+    block_on(pass.write_byte_read_bytes(&[0x68], &mut rbuf)).unwrap();
+
+    // One data-ready poll missed; end of operation already set
+    assert_eq!(sensor.tim.calls, 1);
+    assert_eq!(
+        sensor.tim.total_ns,
+        u64::from(PASSTHROUGH_SETTLE_MS) * 1_000_000
+    );
+}
+
+#[test]
+fn sh_disable_stops_the_master_and_returns_to_the_main_bank() {
+    let mut sensor = running_sensor();
+    block_on(sensor.sh_master_set(1)).unwrap();
+    // Device left in the sensor hub bank by an interrupted access
+    sensor.bus.set_main(
+        Reg::FuncCfgAccess as u8,
+        FuncCfgAccess::new().with_shub_reg_access(1).into_bits(),
+    );
+    sensor.bus.writes.clear();
+
+    block_on(sensor.sh_disable()).unwrap();
+
+    assert_eq!(master_on_writes(&sensor.bus), [0, 0, 0]);
+    let rst_master_regs: Vec<u8> = sensor
+        .bus
+        .writes_to(SENSOR_HUB, MASTER_CONFIG)
+        .iter()
+        .map(|data| MasterConfig::from_bits(data[0]).rst_master_regs())
+        .collect();
+    assert_eq!(rst_master_regs, [0, 1, 0]);
+    assert_eq!(sensor.bus.main(Reg::FuncCfgAccess as u8), 0);
+    // The trigger is left as it was
+    assert!(odr_xl_writes(&sensor.bus).is_empty());
+}
+
+#[test]
+fn sh_data_rate_round_trips_every_variant() {
+    let mut sensor = sensor();
+    let rates = [
+        ShDataRate::_104hz,
+        ShDataRate::_52hz,
+        ShDataRate::_26hz,
+        ShDataRate::_12_5hz,
+    ];
+
+    for rate in rates {
+        assert_eq!(rate.as_bits() & !ShDataRate::FIELD_MASK, 0);
+        block_on(sensor.sh_data_rate_set(rate)).unwrap();
+        assert!(block_on(sensor.sh_data_rate_get()).unwrap() == rate);
+        let slv0_config = sensor.bus.sensor_hub(SensHubReg::Slv0Config as u8);
+        assert_eq!(
+            Slv0Config::from_bits(slv0_config).shub_odr(),
+            rate.as_bits()
+        );
+    }
+}
+
+#[test]
+fn sh_read_split_hands_out_a_12_byte_block_as_6_and_6() {
+    let mut sensor = sensor();
+    let first = SensHubReg::SensorHub1 as usize;
+    for i in 0..12 {
+        sensor.bus.regs[SENSOR_HUB][first + i] = i as u8 + 1;
+    }
+
+    let mut mag = [0u8; 6];
+    let mut baro = [0u8; 8];
+    block_on(sensor.sh_read_split(&[6, 6], &mut [&mut mag, &mut baro])).unwrap();
+
+    assert_eq!(mag, [1, 2, 3, 4, 5, 6]);
+    assert_eq!(baro, [7, 8, 9, 10, 11, 12, 0, 0]);
+}
+
+#[test]
+fn sh_read_split_rejects_inconsistent_layouts() {
+    let mut sensor = sensor();
+    let mut a = [0u8; 12];
+    let mut b = [0u8; 12];
+
+    // Entry count mismatch
+    let result = block_on(sensor.sh_read_split(&[6], &mut [&mut a, &mut b]));
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+    // Buffer shorter than its length
+    let result = block_on(sensor.sh_read_split(&[6, 13], &mut [&mut a, &mut b]));
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+    // Total past the hub window
+    let result = block_on(sensor.sh_read_split(&[12, 12], &mut [&mut a, &mut b]));
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+
+    assert_eq!(sensor.bus.transactions, 0);
+}
+
+#[cfg(feature = "passthrough")]
+#[test]
+fn passthrough_with_interleaves_master_access_and_slave_transfers() {
+    use lsm6dso16is_rs::asynchronous::Lsm6dso16isPassthrough;
+    use st_mems_bus::asynchronous::BusOperation;
+
+    let mut sensor = completing_sensor();
+    sensor.bus.set_main(Reg::WhoAmI as u8, 0x22);
+    sensor.bus.regs[SENSOR_HUB][SensHubReg::SensorHub1 as usize] = 0x40;
+
+    let mut pass = Lsm6dso16isPassthrough::new_from_sensor(&mut sensor, 0x1E);
+    let mut rbuf = [0u8; 1];
+    block_on(pass.write_byte_read_bytes(&[0x68], &mut rbuf)).unwrap();
+    assert_eq!(rbuf, [0x40]);
+
+    // Master configuration between two slave transfers
+    let id = block_on(pass.with(async |s| {
+        s.sh_master_interface_pull_up_set(1).await?;
+        s.device_id_get().await
+    }))
+    .unwrap();
+    assert_eq!(id, 0x22);
+
+    block_on(pass.write_bytes(&[0x20, 0x5A])).unwrap();
+
+    let master_config = MasterConfig::from_bits(sensor.bus.sensor_hub(MASTER_CONFIG));
+    assert_eq!(master_config.shub_pu_en(), 1);
+    assert_eq!(master_config.master_on(), 0);
+    assert_eq!(sensor.bus.main(Reg::FuncCfgAccess as u8), 0);
+    assert_eq!(
+        sensor
+            .bus
+            .writes_to(SENSOR_HUB, SensHubReg::DatawriteSlv0 as u8),
+        [&[0x5A][..]]
+    );
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "async")]
+
+mod common;
+
+use common::{MockBus, MockDelay, block_on};
+use lsm6dso16is_rs::asynchronous::prelude::*;
+use lsm6dso16is_rs::asynchronous::{DataReadySource, Error, Lsm6dso16is, ReinitMode};
+
+fn sensor() -> Lsm6dso16is<MockBus, MockDelay, MainBank> {
+    Lsm6dso16is::from_bus(MockBus::new(), MockDelay::default())
+}
+
+#[test]
+fn read_sample_times_out_when_sensors_are_off() {
+    let mut sensor = sensor();
+
+    assert!(matches!(
+        block_on(sensor.read_sample()),
+        Err(Error::Timeout)
+    ));
+}
+
+#[test]
+fn enable_and_settle_times_out_without_data_ready() {
+    let mut sensor = sensor();
+
+    let result = block_on(sensor.enable_and_settle(XlDataRate::_104hzHp, GyDataRate::Off));
+    assert!(matches!(result, Err(Error::Timeout)));
+}
+
+#[test]
+fn average_samples_times_out_when_sensor_is_off() {
+    let mut sensor = sensor();
+
+    let result =
+        block_on(sensor.average_samples(4, DataReadySource::Gy, Lsm6dso16is::angular_rate_raw_get));
+    assert!(matches!(result, Err(Error::Timeout)));
+}
+
+#[test]
+fn liveness_reports_silent_accelerometer_as_dead() {
+    let mut sensor = sensor();
+    block_on(sensor.xl_data_rate_set(XlDataRate::_104hzHp)).unwrap();
+
+    assert!(!block_on(sensor.accelerometer_liveness(4)).unwrap());
+}
+
+#[test]
+fn acquire_into_soa_times_out_without_data_ready() {
+    let mut sensor = sensor();
+    let (mut x, mut y, mut z) = ([0; 4], [0; 4], [0; 4]);
+
+    let result = block_on(sensor.acquire_into_soa(&mut x, &mut y, &mut z, DataReadySource::Xl));
+    assert!(matches!(result, Err(Error::Timeout)));
+}
+
+#[test]
+fn reinitialize_times_out_when_boot_stays_set() {
+    let mut sensor = sensor();
+
+    let result = block_on(sensor.reinitialize(ReinitMode::RebootMemory));
+    assert!(matches!(result, Err(Error::Timeout)));
+}
+
+#[test]
+fn sh_wait_endop_times_out_and_disables_master() {
+    let mut sensor = sensor();
+    let master_config = SensHubReg::MasterConfig as u8;
+    let master_on =
+        |bus: &MockBus| MasterConfig::from_bits(bus.sensor_hub(master_config)).master_on();
+    block_on(sensor.sh_master_set(1)).unwrap();
+    assert_eq!(master_on(&sensor.bus), 1);
+
+    let result = block_on(sensor.sh_wait_endop(10));
+    assert!(matches!(result, Err(Error::Timeout)));
+    assert_eq!(master_on(&sensor.bus), 0);
+    assert_eq!(sensor.bus.main(Reg::FuncCfgAccess as u8), 0);
+    // Polled every millisecond
+    assert_eq!(sensor.tim.total_ns, 10_000_000);
+}
@@ -0,0 +1,137 @@
+#![cfg(feature = "async")]
+
+mod common;
+
+use common::{MAIN, MockBus, MockDelay, block_on};
+use lsm6dso16is_rs::asynchronous::prelude::*;
+use lsm6dso16is_rs::asynchronous::{Error, Lsm6dso16is};
+
+fn sensor() -> Lsm6dso16is<MockBus, MockDelay, MainBank> {
+    Lsm6dso16is::from_bus(MockBus::new(), MockDelay::default())
+}
+
+/// Position of the first write to `addr` in the write log.
+fn write_index(bus: &MockBus, addr: Reg) -> usize {
+    bus.writes
+        .iter()
+        .position(|(bank, a, _)| *bank == MAIN && *a == addr as u8)
+        .unwrap()
+}
+
+#[test]
+fn timestamp_enable_with_source_starts_the_accelerometer_first() {
+    let mut sensor = sensor();
+
+    block_on(sensor.timestamp_enable_with_source(XlDataRate::_52hzHp)).unwrap();
+
+    let bus = &sensor.bus;
+    assert!(write_index(bus, Reg::Ctrl1Xl) < write_index(bus, Reg::Ctrl10C));
+    assert_eq!(
+        Ctrl1Xl::from_bits(bus.main(Reg::Ctrl1Xl as u8)).odr_xl(),
+        XlDataRate::_52hzHp as u8 & 0x0F
+    );
+    assert_eq!(
+        Ctrl10C::from_bits(bus.main(Reg::Ctrl10C as u8)).timestamp_en(),
+        1
+    );
+}
+
+#[test]
+fn timestamp_enable_with_source_rejects_off() {
+    let mut sensor = sensor();
+
+    let result = block_on(sensor.timestamp_enable_with_source(XlDataRate::Off));
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+    assert!(sensor.bus.writes.is_empty());
+}
+
+fn endcount(flag: u8) -> u8 {
+    StatusReg::new().with_timestamp_endcount(flag).into_bits()
+}
+
+#[test]
+fn timestamp_coherent_get_retries_a_read_across_the_end_count() {
+    let mut sensor = sensor();
+    // The flag toggles around the first read, which is torn (0x2ff for a
+    // 0x1ff -> 0x200 carry); the second read is clean.
+    let status = [endcount(0), endcount(1), endcount(1), endcount(1)];
+    sensor.bus.script(MAIN, Reg::StatusReg as u8, &status);
+    sensor
+        .bus
+        .script(MAIN, Reg::Timestamp0 as u8, &[0xff, 0x00]);
+    sensor
+        .bus
+        .script(MAIN, Reg::Timestamp1 as u8, &[0x02, 0x02]);
+
+    assert_eq!(block_on(sensor.timestamp_coherent_get()).unwrap(), 0x200);
+    assert_eq!(sensor.bus.transactions, 6);
+}
+
+#[test]
+fn timestamp_coherent_get_times_out_if_the_flag_keeps_toggling() {
+    let mut sensor = sensor();
+    let status: Vec<u8> = (0..6).map(|i| endcount(i % 2)).collect();
+    sensor.bus.script(MAIN, Reg::StatusReg as u8, &status);
+
+    let result = block_on(sensor.timestamp_coherent_get());
+    assert!(matches!(result, Err(Error::Timeout)));
+}
+
+#[test]
+fn route_timestamp_overflow_int2_requires_the_counter() {
+    let mut sensor = sensor();
+
+    let result = block_on(sensor.route_timestamp_overflow_int2(true));
+    assert!(matches!(result, Err(Error::UnexpectedValue)));
+    assert!(sensor.bus.writes.is_empty());
+
+    block_on(sensor.timestamp_set(1)).unwrap();
+    block_on(sensor.route_timestamp_overflow_int2(true)).unwrap();
+    let md2_cfg = Md2Cfg::from_bits(sensor.bus.main(Reg::Md2Cfg as u8));
+    assert_eq!(md2_cfg.int2_timestamp(), 1);
+}
+
+#[test]
+fn route_timestamp_overflow_int2_disables_with_the_counter_off() {
+    let mut sensor = sensor();
+    sensor.bus.set_main(
+        Reg::Md2Cfg as u8,
+        Md2Cfg::new().with_int2_timestamp(1).into_bits(),
+    );
+
+    block_on(sensor.route_timestamp_overflow_int2(false)).unwrap();
+    let md2_cfg = Md2Cfg::from_bits(sensor.bus.main(Reg::Md2Cfg as u8));
+    assert_eq!(md2_cfg.int2_timestamp(), 0);
+}
+
+#[test]
+fn freq_fine_get_is_twos_complement() {
+    let mut sensor = sensor();
+    let freq_fine = Reg::InternalFreqFine as u8;
+
+    for (raw, steps) in [(0x80, -128), (0x7F, 127), (0xFF, -1), (0x00, 0)] {
+        sensor.bus.set_main(freq_fine, raw);
+        assert_eq!(block_on(sensor.freq_fine_get()).unwrap(), steps);
+        assert_eq!(block_on(sensor.odr_cal_reg_get()).unwrap(), raw);
+    }
+}
+
+#[test]
+fn timestamp_sync_info_assembles_trim_flag_and_counter() {
+    let mut sensor = sensor();
+    sensor.bus.set_main(Reg::InternalFreqFine as u8, 0xFD);
+    sensor.bus.set_main(Reg::StatusReg as u8, endcount(1));
+    let ts = Reg::Timestamp0 as usize;
+    sensor.bus.regs[MAIN][ts..ts + 4].copy_from_slice(&0x1234_5678u32.to_le_bytes());
+
+    let info = block_on(sensor.timestamp_sync_info()).unwrap();
+
+    assert_eq!(info, (-3, true, 0x1234_5678));
+    assert_eq!(sensor.bus.transactions, 3);
+
+    sensor.bus.set_main(Reg::StatusReg as u8, endcount(0));
+    assert_eq!(
+        block_on(sensor.timestamp_sync_info()).unwrap(),
+        (-3, false, 0x1234_5678)
+    );
+}
@@ -0,0 +1,10 @@
+use embedded_hal_async::delay::DelayNs;
+use lsm6dso16is_rs::asynchronous::Lsm6dso16is;
+use lsm6dso16is_rs::asynchronous::prelude::IspuBank;
+use st_mems_bus::asynchronous::BusOperation;
+
+async fn read_id<B: BusOperation, T: DelayNs>(sensor: &mut Lsm6dso16is<B, T, IspuBank>) {
+    let _ = sensor.device_id_get().await;
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use lsm6dso16is_rs::util::{le_i16, le_i24, le_u24};
+
+#[test]
+fn le_i16_decodes_little_endian() {
+    assert_eq!(le_i16([0x34, 0x12]), 0x1234);
+    assert_eq!(le_i16([0xff, 0xff]), -1);
+    assert_eq!(le_i16([0x00, 0x80]), i16::MIN);
+}
+
+#[test]
+fn le_i24_sign_extends() {
+    assert_eq!(le_i24([0x56, 0x34, 0x12]), 0x12_3456);
+    assert_eq!(le_i24([0xff, 0xff, 0x7f]), 0x7f_ffff);
+    assert_eq!(le_i24([0xff, 0xff, 0xff]), -1);
+    assert_eq!(le_i24([0x00, 0x00, 0x80]), -0x80_0000);
+    assert_eq!(le_i24([0x00, 0xff, 0xff]), -256);
+}
+
+#[test]
+fn le_u24_does_not_sign_extend() {
+    assert_eq!(le_u24([0x56, 0x34, 0x12]), 0x12_3456);
+    assert_eq!(le_u24([0xff, 0xff, 0xff]), 0xff_ffff);
+}